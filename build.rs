@@ -0,0 +1,59 @@
+//! Embeds whichever puzzle inputs exist under `src/inputs` into the binary, as
+//! `src/inputs/<year>/input<day>`.
+//!
+//! Unlike `include_str!`, a missing `src/inputs/<year>/inputN` doesn't break
+//! the build, it just means that day has no embedded input. An empty one is
+//! the same, so a freshly scaffolded day still reads its fetched input.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let inputs_dir = Path::new(&manifest_dir).join("src").join("inputs");
+    println!("cargo:rerun-if-changed={}", inputs_dir.display());
+
+    let mut days = vec![];
+    for (year, year_dir) in numbered_entries(&inputs_dir, "") {
+        println!("cargo:rerun-if-changed={}", year_dir.display());
+        for (day, path) in numbered_entries(&year_dir, "input") {
+            println!("cargo:rerun-if-changed={}", path.display());
+            let empty = fs::metadata(&path).map_or(true, |meta| meta.len() == 0);
+            if !empty {
+                days.push((year as u16, day as u8, path));
+            }
+        }
+    }
+    days.sort();
+
+    let mut generated = String::from("pub const EMBEDDED: &[(u16, u8, &str)] = &[\n");
+    for (year, day, path) in days {
+        writeln!(
+            generated,
+            "    ({}, {}, include_str!({:?})),",
+            year, day, path
+        )
+        .unwrap();
+    }
+    generated.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("embedded_inputs.rs"), generated).unwrap();
+}
+
+/// The entries in `dir` named `prefix` followed by a number, e.g. `input12`
+fn numbered_entries(dir: &Path, prefix: &str) -> Vec<(u32, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let number = name.to_str()?.strip_prefix(prefix)?.parse().ok()?;
+            Some((number, entry.path()))
+        })
+        .collect()
+}
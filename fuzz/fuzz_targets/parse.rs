@@ -0,0 +1,39 @@
+//! Feeds arbitrary input to the days' parsers, which should reject anything malformed with an
+//! error rather than panicking, overflowing or looping forever. The first byte picks which day
+//! to parse, unless `ADVENT_FUZZ_DAY` pins one, which lets a real input seed the corpus:
+//!
+//! ```sh
+//! ADVENT_FUZZ_DAY=2021/16 cargo +nightly fuzz run parse corpus/2021-16 ../src/inputs/2021/input16 -- -timeout=5
+//! ```
+
+#![no_main]
+
+use std::sync::OnceLock;
+
+use advent::runner::{self, Day, DAYS};
+use libfuzzer_sys::fuzz_target;
+
+/// The day `ADVENT_FUZZ_DAY` asks for, like `2021/16`
+fn pinned() -> Option<&'static Day> {
+    static PINNED: OnceLock<Option<&'static Day>> = OnceLock::new();
+    *PINNED.get_or_init(|| {
+        let pinned = std::env::var("ADVENT_FUZZ_DAY").ok()?;
+        let (year, day) = pinned
+            .split_once('/')
+            .expect("ADVENT_FUZZ_DAY should look like 2021/16");
+        let (year, day) = (year.parse().unwrap(), day.parse().unwrap());
+        Some(runner::find_day(year, day).expect("ADVENT_FUZZ_DAY should be a registered day"))
+    })
+}
+
+fuzz_target!(|data: &[u8]| {
+    let (day, data) = match (pinned(), data.split_first()) {
+        (Some(day), _) => (day, data),
+        (None, Some((&n, data))) => (&DAYS[n as usize % DAYS.len()], data),
+        (None, None) => return,
+    };
+    // parsers take a `&str`, so anything else can't reach them
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = day.solver.parse(input);
+    }
+});
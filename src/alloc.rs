@@ -0,0 +1,191 @@
+//! Counting allocations, to see which solutions allocate the most. The binary installs
+//! [`Counting`] as its global allocator, which only counts while something is being
+//! [`measure`]d, so it costs next to nothing the rest of the time.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use serde::Serialize;
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+/// Bytes allocated and not yet freed since counting started, which goes negative if more is
+/// freed than allocated
+static CURRENT: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+/// Only one measurement can have the counters at a time
+static MEASURING: Mutex<()> = Mutex::new(());
+
+/// The system allocator, counting allocations while a measurement is in progress
+pub struct Counting;
+
+impl Counting {
+    fn allocated(&self, size: usize) {
+        if COUNTING.load(Ordering::Relaxed) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            BYTES.fetch_add(size as u64, Ordering::Relaxed);
+            self.grew(size as isize);
+        }
+    }
+
+    fn grew(&self, by: isize) {
+        if COUNTING.load(Ordering::Relaxed) {
+            let current = CURRENT.fetch_add(by, Ordering::Relaxed) + by;
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            self.allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        self.grew(-(layout.size() as isize));
+    }
+
+    /// Counted as a new allocation of the new size, which is usually what it costs
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            self.allocated(new_size);
+            self.grew(-(layout.size() as isize));
+        }
+        new
+    }
+}
+
+/// How much something allocated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Usage {
+    /// Allocations and reallocations
+    pub allocations: u64,
+    /// The total size of every allocation, whether or not it was freed again
+    pub bytes: u64,
+    /// The most memory in use at once, on top of what was in use at the start
+    pub peak: u64,
+}
+
+impl fmt::Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            Bytes(self.bytes),
+            Bytes(self.peak)
+        )
+    }
+}
+
+/// A number of bytes, in whichever unit reads best
+struct Bytes(u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut size = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Run `f`, counting what it allocates, or `None` if [`Counting`] isn't the global allocator.
+/// Every thread's allocations are counted, so anything running at the same time (including a
+/// part that timed out, until it stops) is counted too.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    let _measuring = MEASURING.lock().unwrap_or_else(|e| e.into_inner());
+    if !installed() {
+        return (f(), None);
+    }
+
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+    CURRENT.store(0, Ordering::Relaxed);
+    PEAK.store(0, Ordering::Relaxed);
+    COUNTING.store(true, Ordering::SeqCst);
+    let result = f();
+    COUNTING.store(false, Ordering::SeqCst);
+
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+        peak: PEAK.load(Ordering::Relaxed).max(0) as u64,
+    };
+    (result, Some(usage))
+}
+
+/// Whether [`Counting`] is the global allocator, found by counting an allocation. This flips
+/// the counters on and off, so it's only safe while holding `MEASURING`.
+fn installed() -> bool {
+    let was_counting = COUNTING.swap(true, Ordering::SeqCst);
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    drop(black_box(Box::new(0u8)));
+    let installed = ALLOCATIONS.load(Ordering::Relaxed) != before;
+    COUNTING.store(was_counting, Ordering::SeqCst);
+    installed
+}
+
+#[cfg(test)]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_allocations() {
+        // other tests allocate at the same time, so only the lower bounds are certain
+        let (sum, usage) = measure(|| {
+            let big = black_box(vec![1u8; 1 << 20]);
+            let small: Vec<_> = (0..100).map(|i| black_box(Box::new(i))).collect();
+            big.len() + small.len()
+        });
+        let usage = usage.expect("the counting allocator is installed for tests");
+        assert_eq!(sum, (1 << 20) + 100);
+        assert!(usage.allocations >= 102);
+        assert!(usage.bytes >= (1 << 20) + 800);
+        assert!(usage.peak >= 1 << 20);
+    }
+
+    #[test]
+    fn formats_sizes() {
+        let usage = Usage {
+            allocations: 3,
+            bytes: 5 << 20,
+            peak: 1536,
+        };
+        assert_eq!(
+            usage.to_string(),
+            "3 allocations, 5.0 MiB allocated, 1.5 KiB peak"
+        );
+        assert_eq!(Bytes(1023).to_string(), "1023 B");
+    }
+}
@@ -0,0 +1,313 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::input::InputSource;
+use crate::parse::{self, ParseError, Span};
+use crate::runner::Part;
+
+/// The known correct answers for a set of puzzle inputs, one per year, day and part.
+///
+/// Stored as lines like `2021.13.2: answer`, with `\n` and `\\` escaped so that multi-line answers
+/// (e.g. day 13) fit on one line. Blank lines and lines starting with `#` are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    entries: BTreeMap<(u16, u8, u8), String>,
+}
+
+/// How an answer compares to the recorded one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// There is no recorded answer for this day and part
+    Unknown,
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        let mut entries = BTreeMap::new();
+
+        for line in Span::lines(s) {
+            if line.trim().is_empty() || line.as_str().starts_with('#') {
+                continue;
+            }
+
+            let (key, answer) = parse::rule(line, ": ", "an answer like \"2021.13.2: answer\"")?;
+            let mut numbers = key.split(".");
+            let year = parse::next(&mut numbers, key, "a year")?.parse("a year")?;
+            let day = parse::next(&mut numbers, key, "a \".\" and a day")?.parse("a day")?;
+            let part = parse::next(&mut numbers, key, "a \".\" and a part")?;
+            let part = match part.parse("a part")? {
+                n @ 1..=2 => n,
+                _ => return Err(part.error("part 1 or 2")),
+            };
+            parse::end(&mut numbers)?;
+
+            let answer = unescape(answer)?;
+            if entries.insert((year, day, part), answer).is_some() {
+                let message = format!("only one answer for {}.{}.{}", year, day, part);
+                return Err(key.error(message));
+            }
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Read the answers in `path`, or no answers at all if it doesn't exist yet
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        let s = match std::fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(source) => {
+                return Err(AnswersError::Io {
+                    path: path.to_owned(),
+                    source,
+                })
+            }
+        };
+        Self::parse(&s).map_err(|error| AnswersError::Parse {
+            path: path.to_owned(),
+            error,
+        })
+    }
+
+    /// Write the answers to `path`, keeping everything already there (comments included) and
+    /// adding the answers it doesn't have yet at the end
+    pub fn save(&self, path: &Path) -> Result<(), AnswersError> {
+        let io_error = |source| AnswersError::Io {
+            path: path.to_owned(),
+            source,
+        };
+        let mut text = match std::fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(source) => return Err(io_error(source)),
+        };
+        let saved = Self::parse(&text).map_err(|error| AnswersError::Parse {
+            path: path.to_owned(),
+            error,
+        })?;
+
+        let mut new = self
+            .entries
+            .iter()
+            .filter(|(key, _)| !saved.entries.contains_key(key))
+            .peekable();
+        if new.peek().is_none() {
+            return Ok(());
+        }
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        for (key, answer) in new {
+            text.push_str(&line(key, answer));
+        }
+        std::fs::write(path, text).map_err(io_error)
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: Part) -> Option<&str> {
+        self.entries
+            .get(&(year, day, part.number()))
+            .map(String::as_str)
+    }
+
+    pub fn check(&self, year: u16, day: u8, part: Part, answer: &str) -> Verdict {
+        match self.get(year, day, part) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+
+    /// Remember `answer` as correct, unless there's already an answer for this day and part.
+    /// Returns whether it was recorded.
+    pub fn record(&mut self, year: u16, day: u8, part: Part, answer: &str) -> bool {
+        let key = (year, day, part.number());
+        if self.entries.contains_key(&key) {
+            return false;
+        }
+        self.entries.insert(key, answer.to_string());
+        true
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (key, answer) in &self.entries {
+            write!(f, "{}", line(key, answer))?;
+        }
+        Ok(())
+    }
+}
+
+/// An answer's line in the answers file
+fn line(&(year, day, part): &(u16, u8, u8), answer: &str) -> String {
+    format!("{}.{}.{}: {}\n", year, day, part, escape(answer))
+}
+
+/// Where the answers for a given input source live: next to the inputs themselves
+pub fn default_path(source: &InputSource) -> Option<PathBuf> {
+    match source {
+        InputSource::Embedded | InputSource::Cached(_) => Some(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("src")
+                .join("inputs")
+                .join("answers"),
+        ),
+        InputSource::Dir(dir) | InputSource::OnDisk { inputs: dir, .. } => {
+            Some(dir.join("answers"))
+        }
+        InputSource::File(_) | InputSource::Stdin => None,
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(s: Span) -> Result<String, ParseError> {
+    let mut result = String::with_capacity(s.as_str().len());
+    let mut chars = s.chars();
+    while let Some((_, c)) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some((_, 'n')) => result.push('\n'),
+            Some((_, '\\')) => result.push('\\'),
+            Some((span, _)) => return Err(span.error("\"n\" or \"\\\" after \"\\\"")),
+            None => return Err(s.end_error("\"n\" or \"\\\" after \"\\\"")),
+        }
+    }
+    Ok(result)
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io { path: PathBuf, source: io::Error },
+    Parse { path: PathBuf, error: ParseError },
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswersError::Io { path, source } => {
+                write!(f, "couldn't access {}: {}", path.display(), source)
+            }
+            AnswersError::Parse { path, error } => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_multi_line_answers() {
+        let mut answers = Answers::default();
+        assert!(answers.record(2021, 13, Part::Two, "#.#\n.\\.\n"));
+        assert!(answers.record(2021, 1, Part::One, "1681"));
+        assert!(!answers.record(2021, 1, Part::One, "1"));
+        assert!(answers.record(2020, 1, Part::One, "1"));
+
+        let s = answers.to_string();
+        assert_eq!(
+            s,
+            "2020.1.1: 1\n2021.1.1: 1681\n2021.13.2: #.#\\n.\\\\.\\n\n"
+        );
+        assert_eq!(Answers::parse(&s).unwrap(), answers);
+    }
+
+    #[test]
+    fn saving_keeps_comments() {
+        let path = std::env::temp_dir().join(format!("advent-answers-{}", std::process::id()));
+        let text = "# real inputs\n\n2021.2.1: 150\n\n# the rest\n2021.1.1: 7";
+        std::fs::write(&path, text).unwrap();
+
+        let mut answers = Answers::load(&path).unwrap();
+        answers.save(&path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), text);
+
+        assert!(answers.record(2021, 2, Part::Two, "900"));
+        assert!(answers.record(2020, 1, Part::One, "1"));
+        answers.save(&path).unwrap();
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(saved, format!("{}\n2020.1.1: 1\n2021.2.2: 900\n", text));
+        assert_eq!(Answers::load(&path).unwrap(), answers);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn checks_answers() {
+        let answers = Answers::parse("# real inputs\n\n2021.2.1: 150\n").unwrap();
+        assert_eq!(answers.check(2021, 2, Part::One, "150"), Verdict::Pass);
+        assert_eq!(
+            answers.check(2021, 2, Part::One, "151"),
+            Verdict::Fail {
+                expected: "150".to_string()
+            }
+        );
+        assert_eq!(answers.check(2021, 2, Part::Two, "900"), Verdict::Unknown);
+        assert_eq!(answers.check(2022, 2, Part::One, "150"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn rejects_malformed_entries() {
+        let err = Answers::parse("2021.1.1: 5\n2021.1.3: 7").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 8));
+
+        let err = Answers::parse("2021.1.1 5").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+
+        let err = Answers::parse("2021.1.1: 5\n2021.1.1: 6").unwrap_err();
+        assert_eq!(err.line, 2);
+
+        let err = Answers::parse("2021.13.2: #\\t").unwrap_err();
+        assert_eq!(err.column, 14);
+
+        let err = Answers::parse("13.2: 5").unwrap_err();
+        assert_eq!(err.expected, "a \".\" and a part");
+    }
+
+    #[test]
+    fn embedded_inputs_match_recorded_answers() {
+        let path = default_path(&InputSource::Embedded).unwrap();
+        let answers = Answers::load(&path).unwrap();
+
+        for day in crate::runner::DAYS {
+            let Some(input) = crate::input::embedded(day.year, day.number) else {
+                continue;
+            };
+            let run = crate::runner::run_day(day, input, &[Part::One, Part::Two], None);
+            for outcome in run.result.unwrap() {
+                let answer = outcome.result.unwrap();
+                let verdict = answers.check(day.year, day.number, outcome.part, &answer);
+                assert!(
+                    matches!(verdict, Verdict::Pass | Verdict::Unknown),
+                    "{} part {}: got {}, {:?}",
+                    day,
+                    outcome.part.number(),
+                    answer,
+                    verdict
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn missing_file_means_no_answers() {
+        let path = std::env::temp_dir().join("advent-answers-that-dont-exist");
+        assert_eq!(Answers::load(&path).unwrap(), Answers::default());
+    }
+}
@@ -0,0 +1,480 @@
+//! Benchmark results saved between runs, so a run can be compared with an earlier one (e.g. from
+//! before a change) to see what got faster or slower.
+
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::Measurement;
+
+/// The results of one `advent bench` run, and the code they were measured on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    /// The commit that was checked out, if we could tell
+    pub commit: Option<String>,
+    /// Whether there were uncommitted changes on top of `commit`
+    pub dirty: bool,
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub measurements: Vec<Measurement>,
+}
+
+impl Run {
+    /// A run of the current checkout, made just now
+    pub fn new(measurements: Vec<Measurement>) -> Self {
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+        let commit = git(&["rev-parse", "--short", "HEAD"]);
+        let dirty = commit.is_some()
+            && git(&["status", "--porcelain", "--untracked-files=no"])
+                .is_some_and(|status| !status.is_empty());
+
+        Self {
+            commit,
+            dirty,
+            timestamp: now(),
+            measurements,
+        }
+    }
+
+    /// e.g. `1a2b3c4 (with uncommitted changes), 3 hours ago`
+    pub fn describe(&self) -> String {
+        let commit = match &self.commit {
+            Some(commit) if self.dirty => format!("{} (with uncommitted changes)", commit),
+            Some(commit) => commit.clone(),
+            None => "an unknown commit".to_string(),
+        };
+        format!(
+            "{}, {} ago",
+            commit,
+            age(now().saturating_sub(self.timestamp))
+        )
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn age(seconds: u64) -> String {
+    match seconds {
+        0..=119 => format!("{} seconds", seconds),
+        120..=7199 => format!("{} minutes", seconds / 60),
+        7200..=172_799 => format!("{} hours", seconds / 3600),
+        _ => format!("{} days", seconds / 86400),
+    }
+}
+
+/// Where history is kept unless told otherwise: `bench-history.jsonl` in the crate root
+pub fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("bench-history.jsonl")
+}
+
+/// Every run saved in `path`, oldest first, or none at all if it doesn't exist yet
+pub fn load(path: &Path) -> Result<Vec<Run>, HistoryError> {
+    let s = match std::fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(source) => {
+            return Err(HistoryError::Io {
+                path: path.to_owned(),
+                source,
+            })
+        }
+    };
+
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).map_err(|source| HistoryError::Parse {
+                path: path.to_owned(),
+                line: i + 1,
+                source,
+            })
+        })
+        .collect()
+}
+
+/// Add `run` to the end of the history in `path`, creating it if needed
+pub fn append(path: &Path, run: &Run) -> Result<(), HistoryError> {
+    let line = serde_json::to_string(run).expect("runs are always valid JSON");
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| writeln!(file, "{}", line))
+        .map_err(|source| HistoryError::Io {
+            path: path.to_owned(),
+            source,
+        })
+}
+
+/// The run `name` refers to: `latest`, or the most recent run of a commit starting with `name`
+pub fn find<'a>(runs: &'a [Run], name: &str) -> Option<&'a Run> {
+    if name == "latest" {
+        return runs.last();
+    }
+    runs.iter().rev().find(|run| {
+        run.commit
+            .as_deref()
+            .is_some_and(|commit| !name.is_empty() && commit.starts_with(name))
+    })
+}
+
+/// The run `name` refers to (see [`find`]) in the history in `path`
+pub fn load_run(path: &Path, name: &str) -> Result<Run, HistoryError> {
+    let runs = load(path)?;
+    find(&runs, name)
+        .cloned()
+        .ok_or_else(|| HistoryError::NoMatch {
+            name: name.to_string(),
+            path: path.to_owned(),
+        })
+}
+
+/// What happens to a run's measurements once they've been printed: comparing them with an
+/// earlier run, saving them for later runs, or both
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tracking {
+    path: PathBuf,
+    base: Option<Run>,
+    threshold: f64,
+    save: bool,
+}
+
+impl Tracking {
+    /// Track runs in the history in `path`, comparing them with the run `compare` names, if any
+    pub fn new(
+        path: PathBuf,
+        compare: Option<&str>,
+        threshold: f64,
+        save: bool,
+    ) -> Result<Self, HistoryError> {
+        let base = compare.map(|name| load_run(&path, name)).transpose()?;
+        Ok(Self {
+            path,
+            base,
+            threshold,
+            save,
+        })
+    }
+
+    /// Write how `measurements` compare with the earlier run, then save them. Failing to save is
+    /// reported on stderr. Returns whether they were saved, if they had to be.
+    pub fn finish(&self, out: &mut impl Write, measurements: Vec<Measurement>) -> io::Result<bool> {
+        if let Some(base) = &self.base {
+            writeln!(out, "compared with {}:", base.describe())?;
+            report(out, &compare(base, &measurements, self.threshold))?;
+        }
+        if self.save {
+            if let Err(e) = append(&self.path, &Run::new(measurements)) {
+                eprintln!("{}", e);
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// How one benchmark changed between two runs
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub year: u16,
+    pub day: u8,
+    pub name: String,
+    pub threads: usize,
+    /// The median time in the earlier run, if it had this benchmark
+    pub base_ns: Option<f64>,
+    pub head_ns: f64,
+    pub change: Change,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change {
+    /// Faster by this percentage
+    Faster(f64),
+    /// Slower by this percentage
+    Slower(f64),
+    /// Changed by this percentage, but not by more than the threshold or the noise
+    Unchanged(f64),
+    /// Not in the earlier run
+    New,
+}
+
+/// Compare each of `head`'s measurements with the same benchmark in `base`.
+///
+/// A change only counts if it's more than `threshold` percent of the old median, and if the
+/// medians are further apart than the two runs' standard deviations added together, so noisy
+/// benchmarks need a bigger change to count.
+pub fn compare(base: &Run, head: &[Measurement], threshold: f64) -> Vec<Comparison> {
+    head.iter()
+        .map(|new| {
+            let old = base.measurements.iter().find(|old| {
+                (old.year, old.day, old.name.as_str(), old.threads)
+                    == (new.year, new.day, &new.name, new.threads)
+            });
+            let change = match old {
+                None => Change::New,
+                Some(old) => {
+                    let percent = (new.median_ns - old.median_ns) / old.median_ns * 100.0;
+                    let noise = old.stddev_ns + new.stddev_ns;
+                    if percent.abs() <= threshold || (new.median_ns - old.median_ns).abs() <= noise
+                    {
+                        Change::Unchanged(percent)
+                    } else if percent < 0.0 {
+                        Change::Faster(-percent)
+                    } else {
+                        Change::Slower(percent)
+                    }
+                }
+            };
+            Comparison {
+                year: new.year,
+                day: new.day,
+                name: new.name.clone(),
+                threads: new.threads,
+                base_ns: old.map(|old| old.median_ns),
+                head_ns: new.median_ns,
+                change,
+            }
+        })
+        .collect()
+}
+
+/// Write each comparison, then how many got faster and slower
+pub fn report(out: &mut impl Write, comparisons: &[Comparison]) -> io::Result<()> {
+    let (mut faster, mut slower, mut unchanged, mut new) = (0, 0, 0, 0);
+    for comparison in comparisons {
+        writeln!(out, "{}", comparison)?;
+        match comparison.change {
+            Change::Faster(_) => faster += 1,
+            Change::Slower(_) => slower += 1,
+            Change::Unchanged(_) => unchanged += 1,
+            Change::New => new += 1,
+        }
+    }
+    writeln!(
+        out,
+        "{} faster, {} slower, {} unchanged, {} new",
+        faster, slower, unchanged, new
+    )
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = |ns: f64| Duration::from_nanos(ns as u64);
+        write!(
+            f,
+            "{} day {:<2} {:<11} {:>2} threads ",
+            self.year, self.day, self.name, self.threads
+        )?;
+        match self.base_ns {
+            Some(base) => write!(f, "{:>10.2?} -> {:<10.2?} ", time(base), time(self.head_ns))?,
+            None => write!(f, "{:>10} -> {:<10.2?} ", "", time(self.head_ns))?,
+        }
+        match self.change {
+            Change::Faster(percent) => write!(f, "{:.1}% faster", percent),
+            Change::Slower(percent) => write!(f, "{:.1}% SLOWER", percent),
+            Change::Unchanged(percent) => write!(f, "unchanged ({:+.1}%, within noise)", percent),
+            Change::New => write!(f, "new"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum HistoryError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        line: usize,
+        source: serde_json::Error,
+    },
+    /// No saved run matches the name
+    NoMatch {
+        name: String,
+        path: PathBuf,
+    },
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::Io { path, source } => {
+                write!(f, "couldn't access {}: {}", path.display(), source)
+            }
+            HistoryError::Parse { path, line, source } => {
+                write!(f, "{}, line {}: {}", path.display(), line, source)
+            }
+            HistoryError::NoMatch { name, path } => {
+                write!(f, "no saved run matching {:?} in {}", name, path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(name: &str, threads: usize, median_ns: f64, stddev_ns: f64) -> Measurement {
+        Measurement {
+            year: 2021,
+            day: 5,
+            name: name.to_string(),
+            threads,
+            iterations: 100,
+            samples: 10,
+            median_ns,
+            mean_ns: median_ns,
+            stddev_ns,
+            min_ns: median_ns,
+            max_ns: median_ns,
+        }
+    }
+
+    fn run(commit: &str, measurements: Vec<Measurement>) -> Run {
+        Run {
+            commit: Some(commit.to_string()),
+            dirty: false,
+            timestamp: 0,
+            measurements,
+        }
+    }
+
+    #[test]
+    fn compares_against_thresholds_and_noise() {
+        let base = run(
+            "abc",
+            vec![
+                measurement("part1", 1, 1000.0, 10.0),
+                measurement("part1", 2, 1000.0, 10.0),
+                measurement("part2", 1, 1000.0, 10.0),
+                measurement("parse", 1, 1000.0, 200.0),
+            ],
+        );
+        let head = [
+            measurement("part1", 1, 800.0, 10.0),
+            measurement("part1", 2, 1030.0, 10.0),
+            measurement("part2", 1, 1500.0, 10.0),
+            measurement("parse", 1, 1300.0, 200.0),
+            measurement("fast/part1", 1, 10.0, 1.0),
+        ];
+
+        let changes: Vec<_> = compare(&base, &head, 5.0)
+            .into_iter()
+            .map(|comparison| comparison.change)
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                Change::Faster(20.0),
+                Change::Unchanged(3.0),
+                Change::Slower(50.0),
+                // 30% slower, but only by 1.5 standard deviations
+                Change::Unchanged(30.0),
+                Change::New,
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_runs_by_commit() {
+        let runs = [
+            run("abc123", vec![]),
+            run("def456", vec![]),
+            run("abc123", vec![]),
+        ];
+        assert!(std::ptr::eq(find(&runs, "latest").unwrap(), &runs[2]));
+        assert!(std::ptr::eq(find(&runs, "abc").unwrap(), &runs[2]));
+        assert!(std::ptr::eq(find(&runs, "def456").unwrap(), &runs[1]));
+        assert!(find(&runs, "123").is_none());
+        assert!(find(&runs, "").is_none());
+        assert!(find(&[], "latest").is_none());
+    }
+
+    #[test]
+    fn appends_and_loads() {
+        let path =
+            std::env::temp_dir().join(format!("advent-bench-history-{}", std::process::id()));
+        assert_eq!(load(&path).unwrap(), []);
+
+        let first = run("abc", vec![measurement("part1", 1, 1000.0, 10.0)]);
+        let second = run("def", vec![measurement("part2", 4, 12.5, 0.5)]);
+        append(&path, &first).unwrap();
+        append(&path, &second).unwrap();
+        assert_eq!(load(&path).unwrap(), [first, second]);
+
+        std::fs::write(&path, "{}\n").unwrap();
+        assert!(matches!(
+            load(&path),
+            Err(HistoryError::Parse { line: 1, .. })
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn compares_then_saves() {
+        let path =
+            std::env::temp_dir().join(format!("advent-bench-tracking-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let base = run("abc", vec![measurement("part1", 1, 2_000_000.0, 10.0)]);
+        append(&path, &base).unwrap();
+
+        let err = Tracking::new(path.clone(), Some("def"), 5.0, true).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("no saved run matching \"def\" in {}", path.display())
+        );
+
+        let tracking = Tracking::new(path.clone(), Some("abc"), 5.0, true).unwrap();
+        let head = vec![measurement("part1", 1, 1_000_000.0, 10.0)];
+        let mut out = vec![];
+        assert!(tracking.finish(&mut out, head.clone()).unwrap());
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("compared with abc, "));
+        assert!(out.ends_with("1 faster, 0 slower, 0 unchanged, 0 new\n"));
+        assert_eq!(load_run(&path, "latest").unwrap().measurements, head);
+
+        let mut out = vec![];
+        let tracking = Tracking::new(path.clone(), None, 5.0, false).unwrap();
+        assert!(tracking.finish(&mut out, vec![]).unwrap());
+        assert!(out.is_empty());
+        assert_eq!(load(&path).unwrap().len(), 2);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn describes_comparisons() {
+        let base = run("abc", vec![measurement("part1", 1, 2_000_000.0, 10.0)]);
+        let head = [measurement("part1", 1, 1_000_000.0, 10.0)];
+        assert_eq!(
+            compare(&base, &head, 5.0)[0].to_string(),
+            "2021 day 5  part1        1 threads     2.00ms -> 1.00ms     50.0% faster"
+        );
+
+        let mut out = vec![];
+        report(&mut out, &compare(&base, &head, 5.0)).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.ends_with("50.0% faster\n1 faster, 0 slower, 0 unchanged, 0 new\n"));
+    }
+}
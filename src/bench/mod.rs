@@ -0,0 +1,432 @@
+//! Benchmarks that run on stable Rust: parsing and each part of every day are timed separately,
+//! as is every `fast` variant, and each result can be printed as a line of JSON or saved to the
+//! [`history`] to compare with later runs.
+
+pub mod history;
+
+use std::fmt;
+use std::hint::black_box;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::input::InputSource;
+use crate::parse::ParseError;
+use crate::runner::{self, Day, Part};
+use crate::solution::Solution;
+use crate::y2021::*;
+
+/// However long the budget, take at least this many samples
+const MIN_SAMPLES: usize = 5;
+/// Aim for this many samples in the budget, batching fast routines so each sample is long enough
+/// to time accurately
+const TARGET_SAMPLES: u32 = 50;
+/// Never take more samples than this, however short the routine
+const MAX_SAMPLES: usize = 1000;
+
+/// Times something on the given input, or fails if the input is invalid
+type Routine = dyn Fn(&mut Bencher, &str) -> Result<(), ParseError> + Sync;
+
+/// A single thing to time, e.g. parsing day 5's input
+pub struct Case {
+    pub year: u16,
+    pub day: u8,
+    /// e.g. `parse`, `part1` or `fast/part2`
+    pub name: &'static str,
+    run: Box<Routine>,
+}
+
+impl Case {
+    fn new(
+        year: u16,
+        day: u8,
+        name: &'static str,
+        run: impl Fn(&mut Bencher, &str) -> Result<(), ParseError> + Sync + 'static,
+    ) -> Self {
+        Self {
+            year,
+            day,
+            name,
+            run: Box::new(run),
+        }
+    }
+}
+
+/// The benchmarks for `day`: parsing, each part, and then any `fast` variants
+pub fn cases(day: &'static Day) -> Vec<Case> {
+    let solver = day.solver;
+    let mut cases = vec![Case::new(day.year, day.number, "parse", move |b, input| {
+        solver.parse(input)?;
+        b.iter(|| solver.parse(black_box(input)));
+        Ok(())
+    })];
+    for (name, part) in [("part1", Part::One), ("part2", Part::Two)] {
+        cases.push(Case::new(day.year, day.number, name, move |b, input| {
+            let parsed = solver.parse(input)?;
+            b.iter(|| solver.solve(black_box(parsed.as_ref()), part));
+            Ok(())
+        }));
+    }
+    cases.extend(fast_cases(day.year, day.number));
+    cases
+}
+
+// day 10's `fast` module is still empty, and day 14's fast variant uses the same parser as the
+// original, so it only has the parts
+fn fast_cases(year: u16, day: u8) -> Vec<Case> {
+    match (year, day) {
+        (2021, 3) => vec![
+            Case::new(2021, 3, "fast/parse", |b, input| {
+                day3::fast::parse_input(input)?;
+                b.iter(|| day3::fast::parse_input(black_box(input)));
+                Ok(())
+            }),
+            Case::new(2021, 3, "fast/part1", |b, input| {
+                let (length, nums) = day3::fast::parse_input(input)?;
+                b.iter(|| day3::fast::power_consumption(length, black_box(&nums)));
+                Ok(())
+            }),
+        ],
+        (2021, 4) => vec![
+            Case::new(2021, 4, "fast/parse", |b, input| {
+                day4::fast::Game::parse(input)?;
+                b.iter(|| day4::fast::Game::parse(black_box(input)));
+                Ok(())
+            }),
+            Case::new(2021, 4, "fast/part1", |b, input| {
+                let game = day4::fast::Game::parse(input)?;
+                b.iter_with_setup(|| game.clone(), day4::fast::first_winner_score);
+                Ok(())
+            }),
+        ],
+        (2021, 6) => vec![
+            Case::new(2021, 6, "fast/parse", |b, input| {
+                day6::fast::FishList::parse(input)?;
+                b.iter(|| day6::fast::FishList::parse(black_box(input)));
+                Ok(())
+            }),
+            Case::new(2021, 6, "fast/part1", |b, input| {
+                let fish = day6::fast::FishList::parse(input)?;
+                b.iter_with_setup(|| fish.clone(), |fish| day6::fast::simulate(fish, 80));
+                Ok(())
+            }),
+            Case::new(2021, 6, "fast/part2", |b, input| {
+                let fish = day6::fast::FishList::parse(input)?;
+                b.iter_with_setup(|| fish.clone(), |fish| day6::fast::simulate(fish, 256));
+                Ok(())
+            }),
+        ],
+        (2021, 14) => vec![
+            Case::new(2021, 14, "fast/part1", |b, input| {
+                let question = day14::Day14::parse(input)?;
+                b.iter(|| day14::fast::most_minus_least_common(black_box(&question), 10));
+                Ok(())
+            }),
+            Case::new(2021, 14, "fast/part2", |b, input| {
+                let question = day14::Day14::parse(input)?;
+                b.iter(|| day14::fast::most_minus_least_common(black_box(&question), 40));
+                Ok(())
+            }),
+        ],
+        _ => vec![],
+    }
+}
+
+/// Times a routine, in the style of `test::Bencher`
+pub struct Bencher {
+    budget: Duration,
+    /// Nanoseconds per iteration, one entry per sample
+    samples: Vec<f64>,
+    iterations: u64,
+}
+
+impl Bencher {
+    fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            samples: vec![],
+            iterations: 0,
+        }
+    }
+
+    pub fn iter<T>(&mut self, mut routine: impl FnMut() -> T) {
+        self.iter_with_setup(|| (), |()| routine())
+    }
+
+    /// Time `routine`, but not the `setup` that makes its input, for routines that consume or
+    /// mutate their input
+    pub fn iter_with_setup<I, T>(
+        &mut self,
+        mut setup: impl FnMut() -> I,
+        mut routine: impl FnMut(I) -> T,
+    ) {
+        let mut batch = |size: u64| {
+            let inputs: Vec<I> = (0..size).map(|_| setup()).collect();
+            let start = Instant::now();
+            for input in inputs {
+                black_box(routine(black_box(input)));
+            }
+            start.elapsed()
+        };
+
+        // doubling the batch size until a batch is long enough to time also warms up
+        let sample_time = self.budget / TARGET_SAMPLES;
+        let mut size = 1;
+        while batch(size) < sample_time {
+            size *= 2;
+        }
+
+        let deadline = Instant::now() + self.budget;
+        while self.samples.len() < MIN_SAMPLES
+            || (self.samples.len() < MAX_SAMPLES && Instant::now() < deadline)
+        {
+            let elapsed = batch(size);
+            self.samples.push(elapsed.as_nanos() as f64 / size as f64);
+            self.iterations += size;
+        }
+    }
+}
+
+/// How long a benchmark took per iteration, in nanoseconds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub year: u16,
+    pub day: u8,
+    pub name: String,
+    /// The size of the rayon thread pool it ran in
+    pub threads: usize,
+    pub iterations: u64,
+    pub samples: usize,
+    pub median_ns: f64,
+    pub mean_ns: f64,
+    pub stddev_ns: f64,
+    pub min_ns: f64,
+    pub max_ns: f64,
+}
+
+impl Measurement {
+    fn new(case: &Case, threads: usize, iterations: u64, mut samples: Vec<f64>) -> Self {
+        samples.sort_by(f64::total_cmp);
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2.0
+        } else {
+            samples[n / 2]
+        };
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        Self {
+            year: case.year,
+            day: case.day,
+            name: case.name.to_string(),
+            threads,
+            iterations,
+            samples: n,
+            median_ns: median,
+            mean_ns: mean,
+            stddev_ns: variance.sqrt(),
+            min_ns: samples[0],
+            max_ns: samples[n - 1],
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("measurements are always valid JSON")
+    }
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} day {:<2} {:<11} {:>2} threads {:>12.2?} ± {:<10.2?} ({} iterations)",
+            self.year,
+            self.day,
+            self.name,
+            self.threads,
+            Duration::from_nanos(self.median_ns as u64),
+            Duration::from_nanos(self.stddev_ns as u64),
+            self.iterations
+        )
+    }
+}
+
+/// Run `case` on `input` for roughly `budget`, in a rayon thread pool of `threads` threads if
+/// given, or the global pool if not. Fails with a message if the input is invalid or the case
+/// panics.
+pub fn run(
+    case: &Case,
+    input: &str,
+    budget: Duration,
+    threads: Option<usize>,
+) -> Result<Measurement, String> {
+    let measure = || {
+        let mut bencher = Bencher::new(budget);
+        let result = runner::catch(|| (case.run)(&mut bencher, input));
+        (result, bencher, rayon::current_num_threads())
+    };
+    let (result, bencher, threads) = match threads {
+        Some(threads) => rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .map_err(|e| e.to_string())?
+            .install(measure),
+        None => measure(),
+    };
+
+    match result {
+        Ok(Ok(())) if bencher.samples.is_empty() => Err("nothing was timed".to_string()),
+        Ok(Ok(())) => Ok(Measurement::new(
+            case,
+            threads,
+            bencher.iterations,
+            bencher.samples,
+        )),
+        Ok(Err(e)) => Err(format!("invalid input: {}", e)),
+        Err(message) => Err(format!("panicked: {}", message)),
+    }
+}
+
+/// Run the benchmarks for each day whose names contain `filter`, once per thread pool size,
+/// writing each result as a line of text or of JSON. A day or benchmark that fails is reported
+/// on stderr without stopping the rest. Returns the results, and whether nothing failed.
+pub fn run_days(
+    out: &mut impl Write,
+    days: &[&'static Day],
+    filter: Option<&str>,
+    source: &InputSource,
+    budget: Duration,
+    threads: &[Option<usize>],
+    json: bool,
+) -> io::Result<(Vec<Measurement>, bool)> {
+    let mut succeeded = true;
+    let mut measurements = vec![];
+
+    for day in days {
+        let input = match source.load(day.year, day.number) {
+            Ok(input) => input,
+            Err(e) => {
+                succeeded = false;
+                eprintln!("{}: {}", day, e);
+                continue;
+            }
+        };
+
+        let cases = cases(day);
+        let cases = cases
+            .iter()
+            .filter(|case| filter.is_none_or(|filter| case.name.contains(filter)));
+        for case in cases {
+            for &threads in threads {
+                match run(case, &input, budget, threads) {
+                    Ok(m) if json => {
+                        writeln!(out, "{}", m.to_json())?;
+                        measurements.push(m);
+                    }
+                    Ok(m) => {
+                        writeln!(out, "{}", m)?;
+                        measurements.push(m);
+                    }
+                    Err(e) => {
+                        succeeded = false;
+                        eprintln!("{} {}: {}", day, case.name, e);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok((measurements, succeeded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn case(run: fn(&mut Bencher, &str) -> Result<(), ParseError>) -> Case {
+        Case::new(2000, 99, "test", run)
+    }
+
+    #[test]
+    fn every_day_has_parse_and_both_parts() {
+        for day in runner::DAYS {
+            let names: Vec<_> = cases(day).iter().map(|case| case.name).collect();
+            assert_eq!(names[..3], ["parse", "part1", "part2"], "{}", day);
+        }
+
+        let names: Vec<_> = cases(runner::find_day(2021, 6).unwrap())
+            .iter()
+            .map(|case| case.name)
+            .collect();
+        assert!(names.contains(&"fast/parse"));
+        assert!(names.contains(&"fast/part2"));
+    }
+
+    #[test]
+    fn takes_a_minimum_number_of_samples() {
+        let case = case(|b, input| {
+            b.iter(|| input.len());
+            Ok(())
+        });
+        let measurement = run(&case, "abc", Duration::ZERO, Some(2)).unwrap();
+        assert_eq!(measurement.samples, MIN_SAMPLES);
+        assert_eq!(measurement.iterations, MIN_SAMPLES as u64);
+        assert_eq!(measurement.threads, 2);
+        assert!(measurement.min_ns <= measurement.median_ns);
+        assert!(measurement.median_ns <= measurement.max_ns);
+    }
+
+    #[test]
+    fn setup_runs_once_per_iteration() {
+        let case = case(|b, _| {
+            let mut setups = 0;
+            let mut routines = 0;
+            b.iter_with_setup(|| setups += 1, |()| routines += 1);
+            assert_eq!(setups, routines);
+            assert_eq!(routines as u64, b.iterations + 1);
+            Ok(())
+        });
+        run(&case, "", Duration::ZERO, None).unwrap();
+    }
+
+    #[test]
+    fn reports_failures() {
+        let invalid = case(|_, input| Err(ParseError::end_of_input(input, "a number")));
+        assert_eq!(
+            run(&invalid, "", Duration::ZERO, None),
+            Err("invalid input: line 1, column 1: expected a number, found end of input".into())
+        );
+
+        let panics = case(|_, _| panic!("oh no"));
+        assert_eq!(
+            run(&panics, "", Duration::ZERO, None),
+            Err("panicked: oh no".to_string())
+        );
+
+        let idle = case(|_, _| Ok(()));
+        assert_eq!(
+            run(&idle, "", Duration::ZERO, None),
+            Err("nothing was timed".to_string())
+        );
+    }
+
+    #[test]
+    fn summarizes_samples() {
+        let case = case(|_, _| Ok(()));
+        let measurement = Measurement::new(&case, 1, 40, vec![4.0, 1.0, 3.0, 2.0]);
+        assert_eq!(measurement.median_ns, 2.5);
+        assert_eq!(measurement.mean_ns, 2.5);
+        assert_eq!((measurement.min_ns, measurement.max_ns), (1.0, 4.0));
+        assert!((measurement.stddev_ns - 1.290_994).abs() < 1e-6);
+        assert_eq!(
+            measurement.to_json(),
+            r#"{"year":2000,"day":99,"name":"test","threads":1,"iterations":40,"samples":4,"median_ns":2.5,"mean_ns":2.5,"stddev_ns":1.2909944487358056,"min_ns":1.0,"max_ns":4.0}"#
+        );
+    }
+}
@@ -0,0 +1,383 @@
+//! Differential testing between a day's original solution and its `fast` rewrite: both are run
+//! on the same inputs, and any input they disagree on is shrunk down to a small reproducer.
+
+use std::io::{self, Write};
+use std::panic;
+use std::time::Instant;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::generate;
+use crate::input;
+use crate::parse::ParseError;
+use crate::runner::{self, Part, Record, Variant};
+use crate::solution::Solution;
+use crate::y2021::*;
+
+/// Two implementations of the same part of a day, which should always agree
+pub struct Pair {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub slow: fn(&str) -> Result<String, ParseError>,
+    pub fast: fn(&str) -> Result<String, ParseError>,
+    /// Generates random valid inputs that both implementations can handle
+    pub generate: generate::Generator,
+}
+
+// day 10's `fast` module is still empty, so there's nothing to compare it to yet, and day 4's
+// only finds the first winner
+pub const PAIRS: &[Pair] = &[
+    Pair {
+        year: 2021,
+        day: 3,
+        part: Part::One,
+        slow: part1::<day3::Day3>,
+        fast: |s| day3::fast::solution1(s).map(|n| n.to_string()),
+        generate: generate::day3,
+    },
+    Pair {
+        year: 2021,
+        day: 4,
+        part: Part::One,
+        slow: part1::<day4::Day4>,
+        fast: |s| day4::fast::calculate1(s).map(|n| n.to_string()),
+        generate: generate::day4,
+    },
+    Pair {
+        year: 2021,
+        day: 6,
+        part: Part::One,
+        slow: part1::<day6::Day6>,
+        fast: |s| day6::fast::calculate(s, 80).map(|n| n.to_string()),
+        generate: generate::day6,
+    },
+    Pair {
+        year: 2021,
+        day: 6,
+        part: Part::Two,
+        slow: part2::<day6::Day6>,
+        fast: |s| day6::fast::calculate(s, 256).map(|n| n.to_string()),
+        generate: generate::day6,
+    },
+    Pair {
+        year: 2021,
+        day: 14,
+        part: Part::One,
+        slow: part1::<day14::Day14>,
+        fast: |s| day14::fast::calculate(s, 10).map(|n| n.to_string()),
+        generate: generate::day14,
+    },
+    Pair {
+        year: 2021,
+        day: 14,
+        part: Part::Two,
+        slow: part2::<day14::Day14>,
+        fast: |s| day14::fast::calculate(s, 40).map(|n| n.to_string()),
+        generate: generate::day14,
+    },
+];
+
+fn part1<S: Solution>(input: &str) -> Result<String, ParseError> {
+    S::parse(input).map(|input| S::part1(&input).to_string())
+}
+
+fn part2<S: Solution>(input: &str) -> Result<String, ParseError> {
+    S::parse(input).map(|input| S::part2(&input).to_string())
+}
+
+/// An input the two implementations disagree on, and what each made of it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub input: String,
+    /// The answer, or why there wasn't one (a parse error or panic)
+    pub slow: Result<String, String>,
+    pub fast: Result<String, String>,
+}
+
+impl Pair {
+    /// Run both implementations on `input`, returning how they disagree, if they do.
+    ///
+    /// Both rejecting the input counts as agreeing, even if they reject it for different
+    /// reasons, so that shrinking doesn't get stuck on inputs that are no longer valid.
+    pub fn compare(&self, input: &str) -> Option<Divergence> {
+        let run = |f: fn(&str) -> Result<String, ParseError>| {
+            runner::catch(|| f(input)).and_then(|result| result.map_err(|e| e.to_string()))
+        };
+        let slow = run(self.slow);
+        let fast = run(self.fast);
+
+        let agree = match (&slow, &fast) {
+            (Ok(slow), Ok(fast)) => slow == fast,
+            (Err(_), Err(_)) => true,
+            _ => false,
+        };
+        (!agree).then(|| Divergence {
+            input: input.to_string(),
+            slow,
+            fast,
+        })
+    }
+
+    /// Run just the fast implementation on `input`, timing it
+    pub fn record(&self, input: &str) -> Record {
+        let start = Instant::now();
+        let result = runner::catch(|| (self.fast)(input));
+        let elapsed = start.elapsed();
+        let (answer, error) = match result {
+            Ok(Ok(answer)) => (Some(answer), None),
+            Ok(Err(e)) => (None, Some(format!("invalid input: {}", e))),
+            Err(message) => (None, Some(format!("panicked: {}", message))),
+        };
+        Record {
+            year: self.year,
+            day: self.day,
+            part: Some(self.part.number()),
+            variant: Variant::Fast,
+            answer,
+            parse_ns: None,
+            solve_ns: Some(elapsed.as_nanos() as u64),
+            verdict: None,
+            expected: None,
+            error,
+            parse_memory: None,
+            solve_memory: None,
+        }
+    }
+
+    /// Compare on each input in turn, returning the first divergence, minimized
+    pub fn check(&self, inputs: impl IntoIterator<Item = String>) -> Option<Divergence> {
+        let input = inputs
+            .into_iter()
+            .find(|input| self.compare(input).is_some())?;
+        let minimized = minimize(&input, |candidate| self.compare(candidate).is_some());
+        self.compare(&minimized)
+    }
+
+    /// `count` random inputs of up to `size`, from `seed`
+    pub fn random_inputs(&self, seed: u64, count: usize, size: usize) -> Vec<String> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..count)
+            .map(|_| {
+                let size = rng.gen_range(1..=size.max(1));
+                (self.generate)(&mut rng, size)
+            })
+            .collect()
+    }
+}
+
+/// The pairs for `day` of `year`, or every day of it if there's no `day`. Like `--day` on its
+/// own, the year defaults to the latest when there's a day, and to every year when there isn't.
+pub fn pairs(year: Option<u16>, day: Option<u8>) -> Vec<&'static Pair> {
+    let year = year.or(day.map(|_| runner::latest_year()));
+    PAIRS
+        .iter()
+        .filter(|pair| year.is_none_or(|year| pair.year == year))
+        .filter(|pair| day.is_none_or(|day| pair.day == day))
+        .collect()
+}
+
+/// Check each pair on the day's embedded input, if it has one, then on `cases` random inputs of
+/// up to `size` from `seed`, writing whether they agree or what they disagree on. Returns
+/// whether every pair agreed.
+pub fn check_all(
+    out: &mut impl Write,
+    pairs: &[&Pair],
+    seed: u64,
+    cases: usize,
+    size: usize,
+) -> io::Result<bool> {
+    // shrinking tries lots of broken inputs, so expect (and hide) lots of panics
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let result = (|| {
+        let mut agreed = true;
+        for pair in pairs {
+            let real = input::embedded(pair.year, pair.day).map(str::to_string);
+            let inputs = real
+                .into_iter()
+                .chain(pair.random_inputs(seed, cases, size));
+            match pair.check(inputs) {
+                None => writeln!(
+                    out,
+                    "{} day {} part {}: agree",
+                    pair.year,
+                    pair.day,
+                    pair.part.number()
+                )?,
+                Some(divergence) => {
+                    agreed = false;
+                    writeln!(
+                        out,
+                        "{} day {} part {}: slow and fast disagree on\n{}\nslow: {}\nfast: {}",
+                        pair.year,
+                        pair.day,
+                        pair.part.number(),
+                        divergence.input,
+                        describe(&divergence.slow),
+                        describe(&divergence.fast)
+                    )?;
+                }
+            }
+        }
+        Ok(agreed)
+    })();
+
+    panic::set_hook(hook);
+    result
+}
+
+fn describe(result: &Result<String, String>) -> String {
+    match result {
+        Ok(answer) => answer.clone(),
+        Err(e) => format!("failed: {}", e),
+    }
+}
+
+/// Greedily remove pieces of `input` for as long as `fails` still holds, trying big pieces
+/// (blank-line separated blocks, lines) before small ones (comma separated items, characters)
+pub fn minimize(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut current = input.to_string();
+    'shrink: loop {
+        for candidate in shrink(&current) {
+            if fails(&candidate) {
+                current = candidate;
+                continue 'shrink;
+            }
+        }
+        return current;
+    }
+}
+
+/// Every way of making `input` a little smaller, biggest cuts first
+fn shrink(input: &str) -> Vec<String> {
+    let mut candidates = vec![];
+
+    let blocks: Vec<_> = input.split("\n\n").collect();
+    for i in 0..blocks.len() {
+        candidates.push(without(&blocks, i, "\n\n"));
+    }
+
+    let lines: Vec<_> = input.lines().collect();
+    let mut chunk = lines.len() / 2;
+    while chunk > 0 {
+        for start in (0..lines.len()).step_by(chunk) {
+            let mut shorter = lines[..start].to_vec();
+            shorter.extend(&lines[(start + chunk).min(lines.len())..]);
+            candidates.push(shorter.join("\n"));
+        }
+        chunk /= 2;
+    }
+
+    for (i, line) in lines.iter().enumerate() {
+        let items: Vec<_> = line.split(',').collect();
+        if items.len() > 1 {
+            for j in 0..items.len() {
+                let mut shorter = lines.clone();
+                let line = without(&items, j, ",");
+                shorter[i] = &line;
+                candidates.push(shorter.join("\n"));
+            }
+        }
+    }
+
+    for (i, _) in input.char_indices() {
+        let mut shorter = input.to_string();
+        shorter.remove(i);
+        candidates.push(shorter);
+    }
+
+    candidates.retain(|candidate| candidate.len() < input.len());
+    candidates
+}
+
+fn without(pieces: &[&str], index: usize, separator: &str) -> String {
+    let mut pieces = pieces.to_vec();
+    pieces.remove(index);
+    pieces.join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input;
+
+    use super::*;
+
+    #[test]
+    fn checks_the_selected_pairs() {
+        assert_eq!(pairs(None, None).len(), PAIRS.len());
+        assert!(pairs(Some(2015), None).is_empty());
+        let day3 = pairs(None, Some(3));
+        assert!(!day3.is_empty());
+        assert!(day3.iter().all(|pair| (pair.year, pair.day) == (2021, 3)));
+
+        let mut out = vec![];
+        assert!(check_all(&mut out, &day3, 1, 2, 5).unwrap());
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.lines().count(), day3.len());
+        assert!(out.starts_with("2021 day 3 part 1: agree\n"));
+    }
+
+    #[test]
+    fn every_pair_agrees() {
+        for pair in PAIRS {
+            let real = input::embedded(pair.year, pair.day).map(str::to_string);
+            let inputs = real.into_iter().chain(pair.random_inputs(0, 20, 20));
+            if let Some(divergence) = pair.check(inputs) {
+                let (year, day, part) = (pair.year, pair.day, pair.part.number());
+                panic!("{} day {} part {}: {:?}", year, day, part, divergence);
+            }
+        }
+    }
+
+    fn lines(s: &str) -> Result<String, ParseError> {
+        Ok(s.lines().count().to_string())
+    }
+
+    #[test]
+    fn finds_and_minimizes_divergence() {
+        let pair = Pair {
+            year: 2000,
+            day: 99,
+            part: Part::One,
+            slow: lines,
+            // wrong whenever there's a 7 on a line of its own
+            fast: |s| {
+                assert!(s.lines().all(|line| line != "7"), "oh no");
+                lines(s)
+            },
+            generate: generate::day6,
+        };
+
+        assert_eq!(pair.check(["1\n2".to_string(), "3".to_string()]), None);
+
+        let input = "1,2\n3\n\n4\n7\n5,6".to_string();
+        let divergence = pair.check([input]).unwrap();
+        assert_eq!(divergence.input, "7");
+        assert_eq!(divergence.slow, Ok("1".to_string()));
+        assert_eq!(divergence.fast, Err("oh no".to_string()));
+    }
+
+    #[test]
+    fn records_fast_runs() {
+        let pair = &PAIRS[0];
+        let record = pair.record("101\n010\n111");
+        assert_eq!(
+            (record.variant, record.answer.as_deref()),
+            (Variant::Fast, Some("0"))
+        );
+        assert!(record.error.is_none() && record.solve_ns.is_some());
+
+        let record = pair.record("nope");
+        assert_eq!(record.answer, None);
+        assert!(record.error.unwrap().starts_with("invalid input"));
+    }
+
+    #[test]
+    fn minimize_removes_items_and_characters() {
+        let minimized = minimize("1,2,3\n45,678", |s| s.contains('7') && s.contains('2'));
+        assert_eq!(minimized, "27");
+    }
+}
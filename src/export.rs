@@ -0,0 +1,384 @@
+//! Saving [`visualize`](crate::visualize) frames as images, so a day can be drawn without a
+//! terminal. Each cell becomes a square of pixels: PPM and PNG files hold one frame each, and a
+//! GIF holds every frame as an animation.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::grid::Grid;
+use crate::visualize::{Cell, Frame, Frames, Rgb};
+
+/// An image, one colour per pixel
+pub type Image = Grid<Rgb>;
+
+/// The colour a cell is drawn in: its background if it has one, otherwise its foreground, with
+/// blanks and dots left black so that only what's marked on the grid stands out
+pub fn pixel(cell: &Cell) -> Rgb {
+    match (cell.bg, cell.fg) {
+        (Some(bg), _) => bg,
+        _ if matches!(cell.glyph, ' ' | '.') => Rgb::BLACK,
+        (None, fg) => fg.unwrap_or(Rgb::WHITE),
+    }
+}
+
+/// A frame's cells as squares `scale` pixels wide
+pub fn image(frame: &Frame, scale: usize) -> Image {
+    let cells = &frame.cells;
+    Grid::from_fn(cells.width() * scale, cells.height() * scale, |x, y| {
+        pixel(&cells[(x / scale, y / scale)])
+    })
+}
+
+/// `image` in the top left of a black image `width` by `height`
+fn pad(image: &Image, width: usize, height: usize) -> Image {
+    Grid::from_fn(width, height, |x, y| {
+        image.get(x, y).copied().unwrap_or(Rgb::BLACK)
+    })
+}
+
+/// The red, green and blue bytes of every pixel, row by row
+fn rgb_bytes(image: &Image) -> Vec<u8> {
+    image.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]).collect()
+}
+
+/// A binary PPM, about the simplest image format there is
+pub fn write_ppm(image: &Image, mut out: impl Write) -> io::Result<()> {
+    write!(out, "P6\n{} {}\n255\n", image.width(), image.height())?;
+    out.write_all(&rgb_bytes(image))?;
+    out.flush()
+}
+
+pub fn write_png(image: &Image, out: impl Write) -> Result<(), ExportError> {
+    let (width, height) = dimensions(image, u32::MAX as usize)?;
+    let mut encoder = png::Encoder::new(out, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&rgb_bytes(image))?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// An animated GIF that loops forever, showing each image for `delay` hundredths of a second.
+/// Images smaller than the largest are drawn in its top left corner.
+pub fn write_gif(images: &[Image], delay: u16, out: impl Write) -> Result<(), ExportError> {
+    let width = images.iter().map(Grid::width).max().unwrap_or(0);
+    let height = images.iter().map(Grid::height).max().unwrap_or(0);
+    let (width, height) = dimensions(&Grid::new(width, height, Rgb::BLACK), u16::MAX as usize)?;
+    let palette = palette(images);
+    let mut global = vec![0; palette.len() * 3];
+    for (&Rgb(r, g, b), &i) in &palette {
+        global[i as usize * 3..][..3].copy_from_slice(&[r, g, b]);
+    }
+
+    let mut encoder = gif::Encoder::new(out, width as u16, height as u16, &global)?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+    for image in images {
+        let image = pad(image, width, height);
+        let mut frame = if palette.is_empty() {
+            // speed 10 is the quantizer's default
+            gif::Frame::from_rgb_speed(width as u16, height as u16, &rgb_bytes(&image), 10)
+        } else {
+            // colours come in runs, so most pixels are the same as the one before
+            let mut last = (Rgb::BLACK, 0);
+            let indices: Vec<_> = image
+                .iter()
+                .map(|&color| {
+                    if color != last.0 {
+                        last = (color, palette[&color]);
+                    }
+                    last.1
+                })
+                .collect();
+            gif::Frame::from_indexed_pixels(width as u16, height as u16, indices, None)
+        };
+        frame.delay = delay;
+        encoder.write_frame(&frame)?;
+    }
+    Ok(())
+}
+
+/// Every colour in the images (and the black they're padded with) and its index, or none if
+/// there are too many for a GIF, which then has to approximate them. Drawings seldom use more
+/// than a few dozen, and using them exactly is much quicker than approximating.
+fn palette(images: &[Image]) -> HashMap<Rgb, u8> {
+    let mut palette = HashMap::from([(Rgb::BLACK, 0)]);
+    let mut last = Rgb::BLACK;
+    for &color in images.iter().flat_map(Grid::iter) {
+        if color != last && !palette.contains_key(&color) {
+            if palette.len() == 256 {
+                return HashMap::new();
+            }
+            palette.insert(color, palette.len() as u8);
+        }
+        last = color;
+    }
+    palette
+}
+
+/// The image's size, if it's neither empty nor bigger than `max` either way
+fn dimensions(image: &Image, max: usize) -> Result<(usize, usize), ExportError> {
+    let (width, height) = (image.width(), image.height());
+    if width == 0 || height == 0 || width > max || height > max {
+        return Err(ExportError::Size { width, height });
+    }
+    Ok((width, height))
+}
+
+/// The kinds of image file frames can be saved as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Png,
+    Gif,
+}
+
+impl Format {
+    /// The format a file's extension asks for
+    pub fn of(path: &Path) -> Option<Format> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "ppm" => Some(Format::Ppm),
+            "png" => Some(Format::Png),
+            "gif" => Some(Format::Gif),
+            _ => None,
+        }
+    }
+}
+
+/// Save every frame to `path`, in the format its extension asks for, returning the files
+/// written. A GIF animates every frame at `fps` frames a second; otherwise each frame gets a file
+/// of its own, numbered from 0 after the file's stem (`day11-000.png`, `day11-001.png` and so
+/// on), unless there's only one frame.
+pub fn export(
+    frames: Frames,
+    path: &Path,
+    scale: usize,
+    fps: f64,
+) -> Result<Vec<PathBuf>, ExportError> {
+    let format = Format::of(path).ok_or_else(|| ExportError::UnknownFormat(path.to_owned()))?;
+    let images: Vec<_> = frames.map(|frame| image(&frame, scale.max(1))).collect();
+
+    if format == Format::Gif {
+        // GIF delays are in hundredths of a second, and browsers ignore anything under 2
+        let delay = (100.0 / fps).round().clamp(2.0, u16::MAX as f64) as u16;
+        write_file(path, |out| write_gif(&images, delay, out))?;
+        return Ok(vec![path.to_owned()]);
+    }
+
+    let paths = numbered(path, images.len());
+    for (image, path) in images.iter().zip(&paths) {
+        write_file(path, |out| match format {
+            Format::Ppm => write_ppm(image, out).map_err(|source| ExportError::Io {
+                path: path.clone(),
+                source,
+            }),
+            _ => write_png(image, out),
+        })?;
+    }
+    Ok(paths)
+}
+
+/// `path` if there's only one file, otherwise `count` paths numbered after its stem
+fn numbered(path: &Path, count: usize) -> Vec<PathBuf> {
+    if count == 1 {
+        return vec![path.to_owned()];
+    }
+    let digits = (count - 1).to_string().len().max(3);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = path.extension().unwrap_or_default().to_string_lossy();
+    (0..count)
+        .map(|i| path.with_file_name(format!("{}-{:0digits$}.{}", stem, i, extension)))
+        .collect()
+}
+
+fn write_file(
+    path: &Path,
+    write: impl FnOnce(&mut BufWriter<File>) -> Result<(), ExportError>,
+) -> Result<(), ExportError> {
+    let io_error = |source| ExportError::Io {
+        path: path.to_owned(),
+        source,
+    };
+    let mut out = BufWriter::new(File::create(path).map_err(io_error)?);
+    write(&mut out)?;
+    out.flush().map_err(io_error)
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    /// The file's extension isn't one of the formats
+    UnknownFormat(PathBuf),
+    /// There was nothing to draw, or too much for the format
+    Size {
+        width: usize,
+        height: usize,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Png(png::EncodingError),
+    Gif(gif::EncodingError),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::UnknownFormat(path) => write!(
+                f,
+                "can't tell what kind of image {} is, use .ppm, .png or .gif",
+                path.display()
+            ),
+            ExportError::Size { width, height } => {
+                write!(f, "can't save an image {}x{} pixels", width, height)
+            }
+            ExportError::Io { path, source } => {
+                write!(f, "couldn't write {}: {}", path.display(), source)
+            }
+            ExportError::Png(e) => write!(f, "couldn't encode a PNG: {}", e),
+            ExportError::Gif(e) => write!(f, "couldn't encode a GIF: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<png::EncodingError> for ExportError {
+    fn from(e: png::EncodingError) -> Self {
+        ExportError::Png(e)
+    }
+}
+
+impl From<gif::EncodingError> for ExportError {
+    fn from(e: gif::EncodingError) -> Self {
+        ExportError::Gif(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::visualize;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("advent-export-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn two_cells() -> Frame {
+        let red = Rgb(255, 0, 0);
+        let cells = Grid::from_fn(2, 1, |x, _| match x {
+            0 => Cell::fg('#', red),
+            _ => Cell::fg('.', Rgb::GREY),
+        });
+        Frame::new(cells, "two cells")
+    }
+
+    #[test]
+    fn scales_cells_to_pixels() {
+        let image = image(&two_cells(), 2);
+        assert_eq!((image.width(), image.height()), (4, 2));
+        assert_eq!(
+            image.row(1),
+            [Rgb(255, 0, 0), Rgb(255, 0, 0), Rgb::BLACK, Rgb::BLACK]
+        );
+        assert_eq!(pixel(&Cell::bg('*', Rgb::BLACK, Rgb::WHITE)), Rgb::WHITE);
+        assert_eq!(pixel(&Cell::plain('#')), Rgb::WHITE);
+    }
+
+    #[test]
+    fn writes_ppm() {
+        let mut out = vec![];
+        write_ppm(&image(&two_cells(), 1), &mut out).unwrap();
+        assert_eq!(out, b"P6\n2 1\n255\n\xff\x00\x00\x00\x00\x00");
+    }
+
+    #[test]
+    fn writes_png() {
+        let image = image(&two_cells(), 3);
+        let mut out = vec![];
+        write_png(&image, &mut out).unwrap();
+
+        let mut reader = png::Decoder::new(&out[..]).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (6, 3));
+        assert_eq!(pixels, rgb_bytes(&image));
+
+        let empty = Grid::new(0, 0, Rgb::BLACK);
+        assert!(matches!(
+            write_png(&empty, vec![]),
+            Err(ExportError::Size { .. })
+        ));
+    }
+
+    #[test]
+    fn writes_animated_gifs() {
+        let small = image(&two_cells(), 1);
+        let big = Grid::new(3, 2, Rgb::WHITE);
+        let mut out = vec![];
+        write_gif(&[small, big], 5, &mut out).unwrap();
+
+        let mut decoder = gif::DecodeOptions::new().read_info(&out[..]).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (3, 2));
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((frame.width, frame.height, frame.delay), (3, 2, 5));
+            frames += 1;
+        }
+        assert_eq!(frames, 2);
+    }
+
+    #[test]
+    fn exports_files() {
+        let dir = temp_dir("files");
+        let frames = || -> Frames { Box::new([two_cells(), two_cells(), two_cells()].into_iter()) };
+
+        let paths = export(frames(), &dir.join("day.png"), 2, 10.0).unwrap();
+        let names: Vec<_> = paths.iter().map(|p| p.file_name().unwrap()).collect();
+        assert_eq!(names, ["day-000.png", "day-001.png", "day-002.png"]);
+        assert!(paths.iter().all(|path| path.exists()));
+
+        let one = Box::new(std::iter::once(two_cells()));
+        let paths = export(one, &dir.join("one.ppm"), 1, 10.0).unwrap();
+        assert_eq!(paths, [dir.join("one.ppm")]);
+        assert_eq!(fs::read(&paths[0]).unwrap().len(), 11 + 6);
+
+        let paths = export(frames(), &dir.join("day.GIF"), 2, 10.0).unwrap();
+        assert_eq!(paths, [dir.join("day.GIF")]);
+        assert!(fs::read(&paths[0]).unwrap().starts_with(b"GIF89a"));
+
+        assert!(matches!(
+            export(frames(), &dir.join("day.bmp"), 2, 10.0),
+            Err(ExportError::UnknownFormat(_))
+        ));
+        let missing = dir.join("missing").join("day.png");
+        assert!(matches!(
+            export(frames(), &missing, 2, 10.0),
+            Err(ExportError::Io { .. })
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn exports_the_days() {
+        let frames =
+            visualize::day9("2199943210\n3987894921\n9856789892\n8767896789\n9899965678").unwrap();
+        let images: Vec<_> = frames.map(|frame| image(&frame, 4)).collect();
+        assert_eq!(images.len(), 1);
+        assert_eq!((images[0].width(), images[0].height()), (40, 20));
+        // the low point in the top right is white, and the walls of 9s around it grey
+        assert_eq!(images[0][(39, 0)], Rgb::WHITE);
+        assert_eq!(images[0][(8, 0)], Rgb::GREY);
+    }
+}
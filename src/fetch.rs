@@ -0,0 +1,503 @@
+//! Downloading puzzle inputs from the Advent of Code site into the input cache, which the
+//! default [`InputSource`](crate::input::InputSource) reads from for any day without an embedded
+//! input.
+//!
+//! Inputs never change once a puzzle is unlocked, so a day is only ever downloaded once, and
+//! requests are spaced out so that fetching every day doesn't hammer the site.
+
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::input;
+
+pub const DEFAULT_ENDPOINT: &str = "https://adventofcode.com";
+
+/// The least time to leave between two requests, even from separate runs
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Who's asking, as the site requests automated tools say
+pub const USER_AGENT: &str = "github.com/cameron1024/advent";
+
+/// Makes HTTP requests, so the fetcher can be pointed at a stand-in server, or at no server at
+/// all.
+///
+/// Both methods return the status code and body of the response. Only failing to get a response
+/// at all is an error; error statuses are returned like any other.
+pub trait Http {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<(u16, String), String>;
+
+    /// POST `form` as `application/x-www-form-urlencoded`
+    fn post_form(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        form: &[(&str, &str)],
+    ) -> Result<(u16, String), String>;
+}
+
+/// Real requests, made with `ureq`
+pub struct Ureq(ureq::Agent);
+
+impl Ureq {
+    pub fn new() -> Self {
+        Self(
+            ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(USER_AGENT)
+                .build(),
+        )
+    }
+}
+
+impl Default for Ureq {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Http for Ureq {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<(u16, String), String> {
+        read_response(with_headers(self.0.get(url), headers).call())
+    }
+
+    fn post_form(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        form: &[(&str, &str)],
+    ) -> Result<(u16, String), String> {
+        read_response(with_headers(self.0.post(url), headers).send_form(form))
+    }
+}
+
+fn with_headers(request: ureq::Request, headers: &[(&str, &str)]) -> ureq::Request {
+    headers
+        .iter()
+        .fold(request, |request, (name, value)| request.set(name, value))
+}
+
+fn read_response(result: Result<ureq::Response, ureq::Error>) -> Result<(u16, String), String> {
+    let response = match result {
+        Ok(response) => response,
+        Err(ureq::Error::Status(_, response)) => response,
+        Err(e) => return Err(e.to_string()),
+    };
+    let status = response.status();
+    let body = response.into_string().map_err(|e| e.to_string())?;
+    Ok((status, body))
+}
+
+/// Whether [`Fetcher::fetch`] had to download anything
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already in the cache, at this path
+    Cached(PathBuf),
+    /// The input has just been downloaded into the cache, at this path
+    Downloaded(PathBuf),
+}
+
+/// Downloads inputs into a cache directory laid out like `src/inputs`
+pub struct Fetcher<H> {
+    http: H,
+    endpoint: String,
+    /// The `session` cookie of a logged in browser, only needed if something isn't cached yet
+    session: Option<String>,
+    cache: PathBuf,
+    interval: Duration,
+}
+
+impl<H: Http> Fetcher<H> {
+    pub fn new(http: H, session: Option<String>, cache: PathBuf) -> Self {
+        Self {
+            http,
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            session,
+            cache,
+            interval: DEFAULT_INTERVAL,
+        }
+    }
+
+    /// Fetch from somewhere other than the real site, e.g. `http://localhost:8080`
+    pub fn endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Leave at least `interval` between requests
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Where `day`'s input is (or will be) cached
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        input::path_in(&self.cache, year, day)
+    }
+
+    /// Make sure `day`'s input is in the cache, downloading it if it isn't there yet
+    pub fn fetch(&self, year: u16, day: u8) -> Result<Fetched, FetchError> {
+        let path = self.path(year, day);
+        let dir = path.parent().expect("inputs are in a year's directory");
+        if path.exists() {
+            return Ok(Fetched::Cached(path));
+        }
+        let session = self.session.as_deref().ok_or(FetchError::NoSession)?;
+
+        fs::create_dir_all(dir).map_err(|source| FetchError::Io {
+            path: dir.to_path_buf(),
+            source,
+        })?;
+        self.wait_for_turn()?;
+
+        let url = format!("{}/{}/day/{}/input", self.endpoint, year, day);
+        let cookie = format!("session={}", session);
+        let (status, body) = self
+            .http
+            .get(&url, &[("Cookie", &cookie), ("User-Agent", USER_AGENT)])
+            .map_err(FetchError::Http)?;
+        match status {
+            200 => {}
+            404 => return Err(FetchError::NotUnlocked { year, day }),
+            400 | 401 | 500 if body.contains("log in") => return Err(FetchError::BadSession),
+            429 => return Err(FetchError::RateLimited),
+            _ => return Err(FetchError::Status { status, body }),
+        }
+
+        // write to a temporary file first, so an interrupted download never looks cached
+        let partial = dir.join(format!(".input{}.partial", day));
+        fs::write(&partial, body)
+            .and_then(|()| fs::rename(&partial, &path))
+            .map_err(|source| FetchError::Io {
+                path: path.clone(),
+                source,
+            })?;
+        Ok(Fetched::Downloaded(path))
+    }
+
+    /// Fetch each of `days` in turn, writing where each one is. Failures are reported on stderr,
+    /// and only stop the rest if they're the session's fault, since every other day would fail
+    /// the same way. Returns whether every day was fetched.
+    pub fn fetch_all(&self, out: &mut impl Write, year: u16, days: &[u8]) -> io::Result<bool> {
+        let mut fetched = true;
+        for &day in days {
+            match self.fetch(year, day) {
+                Ok(Fetched::Cached(path)) => writeln!(
+                    out,
+                    "{} day {}: already cached at {}",
+                    year,
+                    day,
+                    path.display()
+                )?,
+                Ok(Fetched::Downloaded(path)) => writeln!(
+                    out,
+                    "{} day {}: downloaded to {}",
+                    year,
+                    day,
+                    path.display()
+                )?,
+                Err(e @ (FetchError::NoSession | FetchError::BadSession)) => {
+                    eprintln!("day {}: {}", day, e);
+                    return Ok(false);
+                }
+                Err(e) => {
+                    fetched = false;
+                    eprintln!("day {}: {}", day, e);
+                }
+            }
+        }
+        Ok(fetched)
+    }
+
+    /// Sleep until `interval` has passed since the last request, which is remembered in the
+    /// cache so separate runs take turns too, then claim this turn
+    fn wait_for_turn(&self) -> Result<(), FetchError> {
+        let path = self.cache.join(".last-request");
+        let last = fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .map(|nanos| UNIX_EPOCH + Duration::from_nanos(nanos));
+        if let Some(since) = last.and_then(|last| SystemTime::now().duration_since(last).ok()) {
+            if since < self.interval {
+                thread::sleep(self.interval - since);
+            }
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        fs::write(&path, now.to_string()).map_err(|source| FetchError::Io { path, source })
+    }
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// There's no session token, and something needs downloading
+    NoSession,
+    /// The site didn't accept the session token
+    BadSession,
+    /// The day's puzzle hasn't been unlocked yet
+    NotUnlocked {
+        year: u16,
+        day: u8,
+    },
+    RateLimited,
+    /// Any other unexpected response
+    Status {
+        status: u16,
+        body: String,
+    },
+    /// No response at all
+    Http(String),
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::NoSession => write!(
+                f,
+                "a session token is needed to download inputs, use --session or $AOC_SESSION"
+            ),
+            FetchError::BadSession => write!(f, "the session token was rejected, log in again"),
+            FetchError::NotUnlocked { year, day } => {
+                write!(f, "{} day {} hasn't been unlocked yet", year, day)
+            }
+            FetchError::RateLimited => write!(f, "too many requests, try again later"),
+            FetchError::Status { status, body } => {
+                let first_line = body.lines().next().unwrap_or("");
+                write!(f, "unexpected response {}: {}", status, first_line)
+            }
+            FetchError::Http(e) => write!(f, "request failed: {}", e),
+            FetchError::Io { path, source } => {
+                write!(f, "couldn't write {}: {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::time::Instant;
+
+    use super::*;
+
+    fn temp_cache(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("advent-fetch-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// A URL and its headers
+    type Request = (String, Vec<(String, String)>);
+
+    /// Answers every request from a list of canned responses, remembering what was asked
+    struct Canned {
+        responses: RefCell<Vec<(u16, String)>>,
+        requests: RefCell<Vec<Request>>,
+    }
+
+    impl Canned {
+        fn new(responses: &[(u16, &str)]) -> Self {
+            Self {
+                responses: RefCell::new(
+                    responses
+                        .iter()
+                        .rev()
+                        .map(|(status, body)| (*status, body.to_string()))
+                        .collect(),
+                ),
+                requests: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl Http for &Canned {
+        fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<(u16, String), String> {
+            let headers = headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect();
+            self.requests.borrow_mut().push((url.to_string(), headers));
+            self.responses
+                .borrow_mut()
+                .pop()
+                .ok_or_else(|| "no more responses".to_string())
+        }
+
+        fn post_form(
+            &self,
+            _: &str,
+            _: &[(&str, &str)],
+            _: &[(&str, &str)],
+        ) -> Result<(u16, String), String> {
+            Err("fetching never posts".to_string())
+        }
+    }
+
+    #[test]
+    fn downloads_once_then_uses_the_cache() {
+        let cache = temp_cache("once");
+        let http = Canned::new(&[(200, "1\n2\n3\n")]);
+        let fetcher = Fetcher::new(&http, Some("abc".into()), cache.clone())
+            .endpoint("http://example.com/")
+            .interval(Duration::ZERO);
+
+        assert_eq!(
+            fetcher.fetch(2021, 3).unwrap(),
+            Fetched::Downloaded(input::path_in(&cache, 2021, 3))
+        );
+        assert_eq!(
+            fetcher.fetch(2021, 3).unwrap(),
+            Fetched::Cached(input::path_in(&cache, 2021, 3))
+        );
+        assert_eq!(
+            fs::read_to_string(input::path_in(&cache, 2021, 3)).unwrap(),
+            "1\n2\n3\n"
+        );
+
+        let requests = http.requests.borrow();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].0, "http://example.com/2021/day/3/input");
+        assert!(requests[0]
+            .1
+            .contains(&("Cookie".to_string(), "session=abc".to_string())));
+        fs::remove_dir_all(cache).unwrap();
+    }
+
+    #[test]
+    fn reports_bad_responses_without_caching_them() {
+        let cache = temp_cache("errors");
+        let http = Canned::new(&[
+            (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!",
+            ),
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+            ),
+            (503, "Service Unavailable\nsorry"),
+        ]);
+        let fetcher =
+            Fetcher::new(&http, Some("abc".into()), cache.clone()).interval(Duration::ZERO);
+
+        assert!(matches!(
+            fetcher.fetch(2021, 25),
+            Err(FetchError::NotUnlocked {
+                year: 2021,
+                day: 25
+            })
+        ));
+        assert!(matches!(
+            fetcher.fetch(2021, 1),
+            Err(FetchError::BadSession)
+        ));
+        let err = fetcher.fetch(2021, 1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unexpected response 503: Service Unavailable"
+        );
+        assert!(!fetcher.path(2021, 1).exists() && !fetcher.path(2021, 25).exists());
+
+        let fetcher = Fetcher::new(&http, None, cache.clone());
+        assert!(matches!(fetcher.fetch(2021, 2), Err(FetchError::NoSession)));
+        fs::remove_dir_all(cache).unwrap();
+    }
+
+    #[test]
+    fn fetches_every_day() {
+        let cache = temp_cache("all");
+        let http = Canned::new(&[(200, "a"), (404, "not yet"), (200, "c")]);
+        let fetcher =
+            Fetcher::new(&http, Some("abc".into()), cache.clone()).interval(Duration::ZERO);
+        fetcher.fetch(2021, 1).unwrap();
+
+        let mut out = vec![];
+        assert!(!fetcher.fetch_all(&mut out, 2021, &[1, 2, 3]).unwrap());
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!(
+                "2021 day 1: already cached at {}\n2021 day 3: downloaded to {}\n",
+                fetcher.path(2021, 1).display(),
+                fetcher.path(2021, 3).display()
+            )
+        );
+        assert!(fetcher.fetch_all(&mut vec![], 2021, &[1, 3]).unwrap());
+
+        // without a session, there's no point trying the rest
+        let fetcher = Fetcher::new(&http, None, cache.clone());
+        assert!(!fetcher.fetch_all(&mut vec![], 2021, &[4, 1]).unwrap());
+        assert_eq!(http.requests.borrow().len(), 3);
+        fs::remove_dir_all(cache).unwrap();
+    }
+
+    #[test]
+    fn spaces_out_requests() {
+        let cache = temp_cache("interval");
+        let http = Canned::new(&[(200, "a"), (200, "b")]);
+        let fetcher = Fetcher::new(&http, Some("abc".into()), cache.clone())
+            .interval(Duration::from_millis(300));
+
+        let start = Instant::now();
+        fetcher.fetch(2021, 1).unwrap();
+        fetcher.fetch(2021, 2).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        fs::remove_dir_all(cache).unwrap();
+    }
+
+    #[test]
+    fn fetches_over_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = vec![];
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push(line.trim().to_string());
+            }
+            let body = "16,1,2,0,4,2,7,1,2,14\n";
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            head
+        });
+
+        let cache = temp_cache("http");
+        let fetcher = Fetcher::new(Ureq::new(), Some("abc".into()), cache.clone())
+            .endpoint(endpoint)
+            .interval(Duration::ZERO);
+        assert!(matches!(fetcher.fetch(2021, 7), Ok(Fetched::Downloaded(_))));
+        assert_eq!(
+            fs::read_to_string(fetcher.path(2021, 7)).unwrap(),
+            "16,1,2,0,4,2,7,1,2,14\n"
+        );
+
+        let head = server.join().unwrap();
+        assert_eq!(head[0], "GET /2021/day/7/input HTTP/1.1");
+        assert!(head.iter().any(|line| line == "Cookie: session=abc"));
+        fs::remove_dir_all(cache).unwrap();
+    }
+}
@@ -0,0 +1,658 @@
+//! Random, well-formed puzzle inputs, for checking solutions against each other and for
+//! benchmarking on more than the one real input. So far only 2021's formats are covered, so the
+//! generators are named after their 2021 day.
+//!
+//! Every generator is deterministic for a given seed, and `size` scales the input roughly
+//! linearly (lines, boards, fish, etc.), so a failure can be reproduced from its seed and size.
+//!
+//! [`mangle`] goes the other way, breaking a well-formed input to check that parsers reject it
+//! without panicking.
+
+use std::collections::VecDeque;
+use std::fmt::Write;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::grid::Grid;
+use crate::solution::Solution;
+use crate::y2021::day11;
+
+/// A generator for one day's input format
+pub type Generator = fn(&mut StdRng, usize) -> String;
+
+/// A seed that's different every run, for when one isn't given
+pub fn random_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+pub fn generator(year: u16, day: u8) -> Option<Generator> {
+    let generator: Generator = match (year, day) {
+        (2021, 1) => day1,
+        (2021, 2) => day2,
+        (2021, 3) => day3,
+        (2021, 4) => day4,
+        (2021, 5) => day5,
+        (2021, 6) => day6,
+        (2021, 7) => day7,
+        (2021, 8) => day8,
+        (2021, 9) => day9,
+        (2021, 10) => day10,
+        (2021, 11) => day11,
+        (2021, 12) => day12,
+        (2021, 13) => day13,
+        (2021, 14) => day14,
+        (2021, 15) => day15,
+        (2021, 16) => day16,
+        _ => return None,
+    };
+    Some(generator)
+}
+
+/// Things that often trip up parsers: huge and negative numbers, separators from other days'
+/// formats, blank lines and multi-byte characters
+const HOSTILE: &[&str] = &[
+    "",
+    "0",
+    "-1",
+    "99999999999999999999999",
+    ",",
+    " ",
+    "\n",
+    "\n\n",
+    " -> ",
+    "-",
+    "=",
+    " | ",
+    "x",
+    "fold along y=",
+    "é",
+    "[",
+    "]",
+];
+
+/// `input` with a few random edits: cut short, with bits missing, repeated, or with
+/// [`HOSTILE`] text inserted or substituted
+pub fn mangle(rng: &mut StdRng, input: &str) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    for _ in 0..rng.gen_range(1..=3) {
+        let at = rng.gen_range(0..=chars.len());
+        let end = rng.gen_range(at..=chars.len().min(at + 10));
+        match rng.gen_range(0..5) {
+            0 => chars.truncate(at),
+            1 => {
+                chars.drain(at..end);
+            }
+            2 => {
+                let repeated = chars[at..end].to_vec();
+                chars.splice(at..at, repeated);
+            }
+            3 => {
+                let hostile = HOSTILE.choose(rng).unwrap().chars();
+                chars.splice(at..at, hostile);
+            }
+            _ => {
+                let hostile = HOSTILE.choose(rng).unwrap().chars();
+                chars.splice(at..end, hostile);
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// `size` sonar depths, drifting up and down
+pub fn day1(rng: &mut StdRng, size: usize) -> String {
+    let mut depth: i32 = rng.gen_range(100..1000);
+    let mut s = String::new();
+    for _ in 0..size {
+        depth = (depth + rng.gen_range(-20..=40)).max(0);
+        writeln!(s, "{}", depth).unwrap();
+    }
+    s
+}
+
+/// `size` submarine commands
+pub fn day2(rng: &mut StdRng, size: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..size {
+        let command = ["forward", "up", "down"].choose(rng).unwrap();
+        writeln!(s, "{} {}", command, rng.gen_range(1..=9)).unwrap();
+    }
+    s
+}
+
+/// `size` distinct binary numbers of up to 16 digits, all the same length
+pub fn day3(rng: &mut StdRng, size: usize) -> String {
+    let size = size.clamp(1, 1 << 16);
+    let min_width = (usize::BITS - (size - 1).leading_zeros()).max(1) as usize;
+    let width = rng.gen_range(min_width..=16);
+
+    // rating the numbers in part 2 only works if they're all different
+    let numbers = rand::seq::index::sample(rng, 1 << width, size);
+    let mut s = String::new();
+    for n in numbers {
+        writeln!(s, "{:0width$b}", n, width = width).unwrap();
+    }
+    s
+}
+
+/// `size` bingo boards, and a draw order that eventually completes all of them, with a single
+/// first and last board to win
+pub fn day4(rng: &mut StdRng, size: usize) -> String {
+    let mut numbers: Vec<u32> = (0..rng.gen_range(25..100)).collect();
+    numbers.shuffle(rng);
+
+    let boards = loop {
+        let boards: Vec<Vec<u32>> = (0..size.max(1))
+            .map(|_| numbers.choose_multiple(rng, 25).copied().collect())
+            .collect();
+
+        let mut wins: Vec<_> = boards.iter().map(|b| bingo_turn(&numbers, b)).collect();
+        wins.sort();
+        let unique_first = wins.len() < 2 || wins[0] != wins[1];
+        let unique_last = wins.len() < 2 || wins[wins.len() - 1] != wins[wins.len() - 2];
+        if unique_first && unique_last {
+            break boards;
+        }
+    };
+
+    let draws: Vec<_> = numbers.iter().map(u32::to_string).collect();
+    let mut s = draws.join(",");
+    s.push('\n');
+
+    for board in boards {
+        s.push('\n');
+        for row in board.chunks(5) {
+            let row: Vec<_> = row.iter().map(|n| format!("{:>2}", n)).collect();
+            writeln!(s, "{}", row.join(" ")).unwrap();
+        }
+    }
+    s
+}
+
+/// The index of the draw on which `board` wins
+fn bingo_turn(draws: &[u32], board: &[u32]) -> usize {
+    let turn = |n: &u32| draws.iter().position(|d| d == n).unwrap();
+    let row = |i: usize| board[i * 5..i * 5 + 5].iter().map(turn).max().unwrap();
+    let col = |i: usize| (0..5).map(|j| turn(&board[i + j * 5])).max().unwrap();
+    (0..5).map(|i| row(i).min(col(i))).min().unwrap()
+}
+
+/// `size` horizontal, vertical and diagonal lines of vents
+pub fn day5(rng: &mut StdRng, size: usize) -> String {
+    let max = (size * 2).clamp(10, 1000);
+    let mut s = String::new();
+    for _ in 0..size {
+        let (x1, y1) = (rng.gen_range(0..max), rng.gen_range(0..max));
+        let (x2, y2) = match rng.gen_range(0..3) {
+            0 => (x1, rng.gen_range(0..max)),
+            1 => (rng.gen_range(0..max), y1),
+            _ => {
+                // as far as possible in a random diagonal direction, then a random distance back
+                let right = rng.gen();
+                let down = rng.gen();
+                let room_x = if right { max - 1 - x1 } else { x1 };
+                let room_y = if down { max - 1 - y1 } else { y1 };
+                let d = rng.gen_range(0..=room_x.min(room_y));
+                let x2 = if right { x1 + d } else { x1 - d };
+                let y2 = if down { y1 + d } else { y1 - d };
+                (x2, y2)
+            }
+        };
+        writeln!(s, "{},{} -> {},{}", x1, y1, x2, y2).unwrap();
+    }
+    s
+}
+
+/// `size` lanternfish timers
+pub fn day6(rng: &mut StdRng, size: usize) -> String {
+    let timers: Vec<_> = (0..size.max(1))
+        .map(|_| rng.gen_range(0..=8u8).to_string())
+        .collect();
+    timers.join(",") + "\n"
+}
+
+/// `size` crab positions
+pub fn day7(rng: &mut StdRng, size: usize) -> String {
+    let max = (size * 4).clamp(10, 2000);
+    let positions: Vec<_> = (0..size.max(1))
+        .map(|_| rng.gen_range(0..max).to_string())
+        .collect();
+    positions.join(",") + "\n"
+}
+
+/// The segments lit for each digit, on a correctly wired display
+const DIGITS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+/// `size` displays, each wired up differently
+pub fn day8(rng: &mut StdRng, size: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..size {
+        let mut wiring: Vec<char> = ('a'..='g').collect();
+        wiring.shuffle(rng);
+        let output_digits: Vec<_> = (0..4).map(|_| DIGITS[rng.gen_range(0..10)]).collect();
+
+        let mut scramble = |digit: &str| {
+            let mut segments: Vec<char> = digit
+                .chars()
+                .map(|c| wiring[c as usize - 'a' as usize])
+                .collect();
+            segments.shuffle(rng);
+            segments.into_iter().collect::<String>()
+        };
+
+        let mut patterns: Vec<_> = DIGITS.iter().map(|digit| scramble(digit)).collect();
+        let output: Vec<_> = output_digits.iter().map(|digit| scramble(digit)).collect();
+        patterns.shuffle(rng);
+        writeln!(s, "{} | {}", patterns.join(" "), output.join(" ")).unwrap();
+    }
+    s
+}
+
+/// A `size` by `size` heightmap, walled off by 9s into basins that each slope down to a single
+/// low point, like the real inputs
+pub fn day9(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(1);
+    // roughly a basin for every 20 locations, grown outwards from random starting points
+    let starts: Vec<_> = (0..size * size / 20 + 1)
+        .map(|_| (rng.gen_range(0..size), rng.gen_range(0..size)))
+        .collect();
+    let mut regions = Grid::new(size, size, None);
+    spread(&mut regions, &starts, |_| true);
+    let region = |position: (usize, usize)| regions[position].map(|(start, _)| start);
+    // where two regions meet, the later one is walled off from the earlier
+    let walls = Grid::from_fn(size, size, |x, y| {
+        regions.neighbors4(x, y).any(|n| region(n) < region((x, y)))
+    });
+
+    // each piece of a region slopes up from its start, or from anywhere if a wall cut it off
+    let open = |position| !walls[position];
+    let mut basins = Grid::new(size, size, None);
+    spread(&mut basins, &starts, open);
+    for position in basins.positions() {
+        spread(&mut basins, &[position], open);
+    }
+
+    let lowest = Grid::from_fn(size, size, |_, _| rng.gen_range(0..=4));
+    basins.render(|basin| match *basin {
+        Some((start, distance)) => {
+            let height = (lowest[start] + distance).min(8);
+            std::char::from_digit(height as u32, 10).unwrap()
+        }
+        None => '9',
+    })
+}
+
+/// `size` lines of chunks, some incomplete and some corrupted
+pub fn day10(rng: &mut StdRng, size: usize) -> String {
+    let mut s = String::new();
+    for i in 0..size.max(1) {
+        let mut line = String::new();
+        let mut open = vec![];
+        let length = rng.gen_range(4..=40);
+        while line.len() < length {
+            let close = !open.is_empty() && rng.gen_bool(0.4);
+            if close {
+                line.push(open.pop().unwrap());
+            } else {
+                let (opening, closing) = *[('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')]
+                    .choose(rng)
+                    .unwrap();
+                line.push(opening);
+                open.push(closing);
+            }
+        }
+
+        // part 2 needs at least one incomplete line, so never corrupt the first
+        if i > 0 && rng.gen_bool(0.5) {
+            let wrong: Vec<_> = ")]}>".chars().filter(|c| open.last() != Some(c)).collect();
+            line.push(*wrong.choose(rng).unwrap());
+        } else if open.is_empty() {
+            line.push('(');
+        }
+        writeln!(s, "{}", line).unwrap();
+    }
+    s
+}
+
+/// A grid of octopuses, which is always 10 by 10 whatever the size, and which synchronizes
+/// eventually (not all of them do)
+pub fn day11(rng: &mut StdRng, _size: usize) -> String {
+    loop {
+        let s = digit_grid(rng, 10, 0..=9);
+        let grid = day11::Day11::parse(&s).unwrap();
+        if day11::synchronizes_within(&grid, 1000) {
+            return s;
+        }
+    }
+}
+
+/// A cave system with `size` connections. The number of paths through it grows very quickly
+/// with size.
+pub fn day12(rng: &mut StdRng, size: usize) -> String {
+    let name = |rng: &mut StdRng, range: std::ops::RangeInclusive<u8>| {
+        (0..2)
+            .map(|_| rng.gen_range(range.clone()) as char)
+            .collect::<String>()
+    };
+    let large: Vec<String> = (0..(size / 6).max(1))
+        .map(|_| name(rng, b'A'..=b'Z'))
+        .collect();
+    let mut small: Vec<String> = (0..(size / 3).max(1))
+        .map(|_| name(rng, b'a'..=b'z'))
+        .collect();
+    small.retain(|cave| cave != "start" && cave != "end");
+    small.push("start".to_string());
+    small.push("end".to_string());
+
+    let mut s = String::new();
+    for i in 0..size.max(2) {
+        // make sure both ends are connected to something
+        let a = match i {
+            0 => "start",
+            1 => "end",
+            _ => small.choose(rng).unwrap(),
+        };
+        // large caves may never be connected to each other, or paths could loop forever
+        let b = if rng.gen() {
+            large.choose(rng).unwrap()
+        } else {
+            small.choose(rng).unwrap()
+        };
+        let b = match (a == b, i) {
+            (false, _) => b,
+            (true, 0) => "end",
+            (true, 1) => "start",
+            (true, _) => continue,
+        };
+        if rng.gen() {
+            writeln!(s, "{}-{}", a, b).unwrap();
+        } else {
+            writeln!(s, "{}-{}", b, a).unwrap();
+        }
+    }
+    s
+}
+
+/// `size` dots on a piece of paper, and some folds which always fold it exactly in half
+pub fn day13(rng: &mut StdRng, size: usize) -> String {
+    // work backwards from the folded paper, unfolding it each time
+    let (mut width, mut height) = (rng.gen_range(4..=40), rng.gen_range(4..=8));
+    let mut folds = vec![];
+    for _ in 0..rng.gen_range(1..=6) {
+        if rng.gen() {
+            folds.push(('x', width));
+            width = width * 2 + 1;
+        } else {
+            folds.push(('y', height));
+            height = height * 2 + 1;
+        }
+    }
+    folds.reverse();
+
+    // dots never land on a fold line, at any point of the folding
+    let on_a_fold = |mut x: usize, mut y: usize| {
+        for &(axis, n) in &folds {
+            let coord = if axis == 'x' { &mut x } else { &mut y };
+            if *coord == n {
+                return true;
+            }
+            if *coord > n {
+                *coord = 2 * n - *coord;
+            }
+        }
+        false
+    };
+
+    let mut s = String::new();
+    let mut dots = 0;
+    while dots < size.max(1) {
+        let (x, y) = (rng.gen_range(0..width), rng.gen_range(0..height));
+        if !on_a_fold(x, y) {
+            writeln!(s, "{},{}", x, y).unwrap();
+            dots += 1;
+        }
+    }
+    s.push('\n');
+    for (axis, n) in folds {
+        writeln!(s, "fold along {}={}", axis, n).unwrap();
+    }
+    s
+}
+
+/// A template of `size` elements, with an insertion rule for every pair of elements
+pub fn day14(rng: &mut StdRng, size: usize) -> String {
+    let mut alphabet: Vec<char> = ('A'..='Z').collect();
+    alphabet.shuffle(rng);
+    let elements = &alphabet[..rng.gen_range(2..=10)];
+
+    let mut s: String = (0..size.max(2))
+        .map(|_| *elements.choose(rng).unwrap())
+        .collect();
+    s.push_str("\n\n");
+
+    for a in elements {
+        for b in elements {
+            writeln!(s, "{}{} -> {}", a, b, elements.choose(rng).unwrap()).unwrap();
+        }
+    }
+    s
+}
+
+/// A `size` by `size` grid of risk levels
+pub fn day15(rng: &mut StdRng, size: usize) -> String {
+    digit_grid(rng, size.max(1), 1..=9)
+}
+
+/// A transmission of about `size` packets, whose value fits in a `u64`
+pub fn day16(rng: &mut StdRng, size: usize) -> String {
+    let mut budget = size.max(1);
+    let mut bits = packet(rng, &mut budget, 0);
+    while !bits.len().is_multiple_of(4) {
+        bits.push('0');
+    }
+
+    bits.as_bytes()
+        .chunks(4)
+        .map(|nibble| {
+            let nibble = std::str::from_utf8(nibble).unwrap();
+            let digit = u32::from_str_radix(nibble, 2).unwrap();
+            std::char::from_digit(digit, 16)
+                .unwrap()
+                .to_ascii_uppercase()
+        })
+        .chain(['\n'])
+        .collect()
+}
+
+/// The bits of a random packet, using up some of the `budget` of packets
+fn packet(rng: &mut StdRng, budget: &mut usize, depth: usize) -> String {
+    *budget = budget.saturating_sub(1);
+    let version = rng.gen_range(0..8);
+
+    if *budget == 0 || depth > 8 || rng.gen_bool(0.3) {
+        let bits = rng.gen_range(1..16);
+        return literal(version, rng.gen_range(0..1 << bits));
+    }
+
+    let type_id = *[0, 1, 2, 3, 5, 6, 7].choose(rng).unwrap();
+    let children: Vec<String> = match type_id {
+        // keep products small so that nothing overflows
+        1 => (0..rng.gen_range(1..=3))
+            .map(|_| literal(rng.gen_range(0..8), rng.gen_range(0..1000)))
+            .collect(),
+        5..=7 => (0..2).map(|_| packet(rng, budget, depth + 1)).collect(),
+        _ => {
+            let count = rng.gen_range(1..=(*budget).clamp(1, 5));
+            (0..count).map(|_| packet(rng, budget, depth + 1)).collect()
+        }
+    };
+
+    let mut bits = format!("{:03b}{:03b}", version, type_id);
+    let count = children.len();
+    let children = children.concat();
+    if children.len() < 1 << 15 && rng.gen() {
+        write!(bits, "0{:015b}", children.len()).unwrap();
+    } else {
+        write!(bits, "1{:011b}", count).unwrap();
+    }
+    bits + &children
+}
+
+fn literal(version: u8, value: u64) -> String {
+    let mut bits = format!("{:03b}100", version);
+    let groups = format!("{:b}", value);
+    let padding = (4 - groups.len() % 4) % 4;
+    let groups = "0".repeat(padding) + &groups;
+    let chunks: Vec<_> = groups.as_bytes().chunks(4).collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        bits.push(if i + 1 == chunks.len() { '0' } else { '1' });
+        bits.push_str(std::str::from_utf8(chunk).unwrap());
+    }
+    bits
+}
+
+/// A square grid of random digits in `range`
+/// Label every `open` location reachable from `starts` with the nearest start and how far away
+/// it is, growing out from all of them at once. Locations that are already labelled are left
+/// alone.
+fn spread(
+    labels: &mut Grid<Option<((usize, usize), usize)>>,
+    starts: &[(usize, usize)],
+    open: impl Fn((usize, usize)) -> bool,
+) {
+    let mut queue = VecDeque::new();
+    for &start in starts {
+        if open(start) && labels[start].is_none() {
+            labels[start] = Some((start, 0));
+            queue.push_back(start);
+        }
+    }
+    while let Some((x, y)) = queue.pop_front() {
+        let (start, distance) = labels[(x, y)].unwrap();
+        for next in labels.neighbors4(x, y) {
+            if open(next) && labels[next].is_none() {
+                labels[next] = Some((start, distance + 1));
+                queue.push_back(next);
+            }
+        }
+    }
+}
+
+fn digit_grid(rng: &mut StdRng, size: usize, range: std::ops::RangeInclusive<u32>) -> String {
+    let mut s = String::with_capacity((size + 1) * size);
+    for _ in 0..size {
+        for _ in 0..size {
+            s.push(std::char::from_digit(rng.gen_range(range.clone()), 10).unwrap());
+        }
+        s.push('\n');
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use std::collections::HashSet;
+
+    use crate::runner::{self, Part};
+    use crate::y2021::day9;
+
+    use super::*;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for day in runner::DAYS {
+            // a freshly scaffolded day has no generator yet
+            let Some(generate) = generator(day.year, day.number) else {
+                continue;
+            };
+            for seed in 0..20 {
+                let input = generate(&mut StdRng::seed_from_u64(seed), seed as usize);
+                if let Err(e) = day.solver.parse(&input) {
+                    panic!("{} seed {}: {}\n{}", day, seed, e, input);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        // day 12's number of paths can be huge, even for small inputs
+        for number in [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 14, 15, 16] {
+            let day = runner::find_day(2021, number).unwrap();
+            let generate = generator(2021, number).unwrap();
+            for seed in 0..10 {
+                let input = generate(&mut StdRng::seed_from_u64(seed), 10);
+                let run = runner::run_day(day, &input, &[Part::One, Part::Two], None);
+                for outcome in run.result.unwrap() {
+                    if let Err(e) = outcome.result {
+                        panic!("day {} seed {}: {}\n{}", number, seed, e, input);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mangled_inputs_dont_panic_parsers() {
+        for day in runner::DAYS {
+            // a freshly scaffolded day has no generator yet
+            let Some(generate) = generator(day.year, day.number) else {
+                continue;
+            };
+            // some generators are slow, so mangle each input lots of ways
+            for seed in 0..5 {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let input = generate(&mut rng, 5);
+                for _ in 0..40 {
+                    let mangled = mangle(&mut rng, &input);
+                    if let Err(message) = runner::catch(|| day.solver.parse(&mangled)) {
+                        panic!("{} seed {} panicked: {}\n{:?}", day, seed, message, mangled);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        for day in runner::DAYS {
+            let Some(generator) = generator(day.year, day.number) else {
+                continue;
+            };
+            let generate = |seed| generator(&mut StdRng::seed_from_u64(seed), 5);
+            assert_eq!(generate(7), generate(7), "{}", day);
+        }
+        let generate = |seed| day4(&mut StdRng::seed_from_u64(seed), 3);
+        assert_ne!(generate(7), generate(8));
+    }
+
+    #[test]
+    fn day9_basins_have_one_low_point() {
+        for seed in 0..20 {
+            let input = day9(&mut StdRng::seed_from_u64(seed), seed as usize * 3);
+            let grid = day9::parse_heights(&input).unwrap();
+            let low_points: HashSet<_> = day9::low_points(&grid).collect();
+            let basins = day9::basins(&grid);
+            for basin in &basins {
+                let low = basin.iter().filter(|p| low_points.contains(p)).count();
+                assert_eq!(low, 1, "seed {}\n{}", seed, input);
+            }
+            // and every location outside the walls is in a basin
+            let sizes: usize = basins.iter().map(HashSet::len).sum();
+            assert_eq!(sizes, grid.iter().filter(|&&height| height < 9).count());
+        }
+    }
+
+    #[test]
+    fn day16_literal() {
+        assert_eq!(literal(6, 2021), "110100101111111000101");
+    }
+}
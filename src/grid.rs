@@ -0,0 +1,352 @@
+use std::ops::{Index, IndexMut, RangeInclusive};
+
+use crate::parse::{ParseError, Span};
+
+/// Left, right, up, down
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// Every neighbour including diagonals, row by row
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid, indexed by `(x, y)` with `(0, 0)` in the top left, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid with every cell set to `value`
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// A grid where the cell at `(x, y)` is `f(x, y)`
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.contains(x, y).then(|| &self.cells[x + y * self.width])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.contains(x, y) {
+            Some(&mut self.cells[x + y * self.width])
+        } else {
+            None
+        }
+    }
+
+    /// The cell at `(x, y)`, as if the grid repeated forever in every direction
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self[(x, y)]
+    }
+
+    /// Every `(x, y)` in the grid, row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell, row by row, in the same order as [`Grid::positions`]
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on 0, but a grid with no columns has no cells to chunk anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} of a {} wide grid", x, self.width);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// The positions left, right, above and below `(x, y)` that are inside the grid
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(x, y, &ORTHOGONAL)
+    }
+
+    /// The positions around `(x, y)`, including diagonally, that are inside the grid
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(x, y, &SURROUNDING)
+    }
+
+    fn offsets(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let x = x.checked_add_signed(dx).filter(|&x| x < width)?;
+            let y = y.checked_add_signed(dy).filter(|&y| y < height)?;
+            Some((x, y))
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// One line per row, drawing each cell as a character
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows().take(self.height) {
+            s.extend(row.iter().map(&mut f));
+            s.push('\n');
+        }
+        s
+    }
+
+    /// A grid with one row per non-empty line and one cell per character, all rows the same
+    /// length. `cells` names what the cells are, e.g. `heights`, for errors about the rows.
+    pub fn parse(
+        s: &str,
+        cells: &str,
+        mut cell: impl FnMut(Span<'_>, char) -> Result<T, ParseError>,
+    ) -> Result<Self, ParseError> {
+        let mut lines = Span::lines(s).filter(|line| !line.is_empty()).peekable();
+        let width = match lines.peek() {
+            Some(line) => line.as_str().chars().count(),
+            None => return Err(ParseError::end_of_input(s, format!("a row of {}", cells))),
+        };
+
+        let mut grid = Self {
+            width,
+            height: 0,
+            cells: vec![],
+        };
+        for line in lines {
+            let mut chars = line.chars();
+            for _ in 0..width {
+                let (c, char) = chars
+                    .next()
+                    .ok_or_else(|| line.end_error(format!("a row of {} {}", width, cells)))?;
+                grid.cells.push(cell(c, char)?);
+            }
+            if let Some((c, _)) = chars.next() {
+                return Err(c.error(format!("a row of {} {}", width, cells)));
+            }
+            grid.height += 1;
+        }
+        Ok(grid)
+    }
+
+    /// A grid of single digits in `digits`. `cell` names a single digit, e.g. `height`, and is
+    /// made plural with an "s" for errors about the rows.
+    pub fn parse_digits(s: &str, cell: &str, digits: RangeInclusive<u8>) -> Result<Self, ParseError>
+    where
+        T: From<u8>,
+    {
+        let article = if cell.starts_with(['a', 'e', 'i', 'o', 'u']) {
+            "an"
+        } else {
+            "a"
+        };
+        let expected = format!(
+            "{} {} from {} to {}",
+            article,
+            cell,
+            digits.start(),
+            digits.end()
+        );
+        Self::parse(s, &format!("{}s", cell), |c, char| {
+            char.to_digit(10)
+                .map(|digit| digit as u8)
+                .filter(|digit| digits.contains(digit))
+                .map(T::from)
+                .ok_or_else(|| c.error(&expected))
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        match self.get(x, y) {
+            Some(cell) => cell,
+            None => panic!(
+                "({}, {}) is outside a {}x{} grid",
+                x, y, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(x, y) {
+            Some(cell) => cell,
+            None => panic!("({}, {}) is outside a {}x{} grid", x, y, width, height),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered(width: usize, height: usize) -> Grid<usize> {
+        Grid::from_fn(width, height, |x, y| x + 10 * y)
+    }
+
+    #[test]
+    fn indexing() {
+        let mut grid = numbered(3, 2);
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 12);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+
+        grid[(1, 1)] = 99;
+        *grid.get_mut(0, 0).unwrap() = 42;
+        assert_eq!(
+            grid.iter().copied().collect::<Vec<_>>(),
+            [42, 1, 2, 10, 99, 12]
+        );
+        assert!(grid.get_mut(5, 5).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "(3, 0) is outside a 3x2 grid")]
+    fn indexing_out_of_bounds_panics() {
+        let _ = numbered(3, 2)[(3, 0)];
+    }
+
+    #[test]
+    fn wrapping() {
+        let grid = numbered(3, 2);
+        assert_eq!(*grid.get_wrapping(4, 3), 11);
+        assert_eq!(*grid.get_wrapping(-1, -1), 12);
+        assert_eq!(*grid.get_wrapping(-7, 2), 2);
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = numbered(3, 3);
+        let four: Vec<_> = grid.neighbors4(0, 0).collect();
+        assert_eq!(four, [(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors4(1, 1).count(), 4);
+
+        let eight: Vec<_> = grid.neighbors8(2, 1).collect();
+        assert_eq!(eight, [(1, 0), (2, 0), (1, 1), (1, 2), (2, 2)]);
+        assert_eq!(grid.neighbors8(1, 1).count(), 8);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = numbered(3, 2);
+        assert_eq!(grid.row(1), [10, 11, 12]);
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[0, 1, 2], [10, 11, 12]]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), [2, 12]);
+
+        let columns: Vec<Vec<_>> = grid.columns().map(|c| c.copied().collect()).collect();
+        assert_eq!(columns, [[0, 10], [1, 11], [2, 12]]);
+        assert_eq!(
+            grid.positions().collect::<Vec<_>>(),
+            [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn parses_and_renders_char_maps() {
+        let grid = Grid::parse("#.\n.#\n\n", "dots", |c, char| match char {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(c.error("\"#\" or \".\"")),
+        })
+        .unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert!(grid[(1, 1)] && !grid[(1, 0)]);
+        assert_eq!(grid.render(|&dot| if dot { '#' } else { '.' }), "#.\n.#\n");
+        assert_eq!(grid.map(|&dot| dot as u8).iter().sum::<u8>(), 2);
+    }
+
+    #[test]
+    fn parses_digits() {
+        let grid = Grid::<u64>::parse_digits("123\n456", "risk level", 1..=9).unwrap();
+        assert_eq!(grid[(2, 1)], 6);
+
+        let err = Grid::<u8>::parse_digits("123\n406", "risk level", 1..=9).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "a risk level from 1 to 9");
+
+        let err = Grid::<u8>::parse_digits("123\n45", "height", 0..=9).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "a row of 3 heights");
+
+        let err = Grid::<u8>::parse_digits("123\n4567", "energy level", 0..=9).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.expected, "a row of 3 energy levels");
+
+        let err = Grid::<u8>::parse_digits("1x", "energy level", 0..=9).unwrap_err();
+        assert_eq!(err.expected, "an energy level from 0 to 9");
+
+        let err = Grid::<u8>::parse_digits("\n", "height", 0..=9).unwrap_err();
+        assert_eq!(
+            (err.expected.as_str(), err.found.as_str()),
+            ("a row of heights", "end of input")
+        );
+    }
+}
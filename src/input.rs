@@ -0,0 +1,226 @@
+use std::borrow::Cow;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+// generated by build.rs from the contents of `src/inputs`
+include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+
+/// The input that was compiled into the binary for a given day, if any
+pub fn embedded(year: u16, day: u8) -> Option<&'static str> {
+    EMBEDDED
+        .iter()
+        .find(|&&(y, number, _)| (y, number) == (year, day))
+        .map(|(_, _, input)| *input)
+}
+
+/// Where a day's input lives in a directory laid out like `src/inputs`
+pub fn path_in(dir: &Path, year: u16, day: u8) -> PathBuf {
+    dir.join(year.to_string()).join(format!("input{}", day))
+}
+
+/// This crate's `src/inputs`, which the embedded inputs were read from when it was built
+pub fn source_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("inputs")
+}
+
+/// Where to read a day's puzzle input from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// The inputs under `src/inputs` at compile time
+    Embedded,
+    /// The embedded inputs, then whatever has been fetched into this cache directory for days
+    /// without one
+    Cached(PathBuf),
+    /// A single file, used whichever day is being run
+    File(PathBuf),
+    /// Standard input, used whichever day is being run
+    Stdin,
+    /// A directory laid out like `src/inputs`, i.e. with a directory per year containing
+    /// `input1`, `input2`, etc.
+    Dir(PathBuf),
+    /// Like `Cached`, but reading `inputs` (e.g. `src/inputs`) as it is now rather than as it was
+    /// compiled in, so that edits show up without rebuilding. Empty files are skipped, just as
+    /// they're never embedded.
+    OnDisk { inputs: PathBuf, cache: PathBuf },
+}
+
+impl InputSource {
+    /// `-` means stdin, anything else is a file
+    pub fn from_arg(s: &str) -> Self {
+        if s == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(s.into())
+        }
+    }
+
+    /// Whether the same input is returned whatever day is asked for
+    pub fn is_single(&self) -> bool {
+        matches!(self, InputSource::File(_) | InputSource::Stdin)
+    }
+
+    pub fn load(&self, year: u16, day: u8) -> Result<Cow<'static, str>, InputError> {
+        match self {
+            InputSource::Embedded => embedded(year, day)
+                .map(Cow::Borrowed)
+                .ok_or(InputError::Missing { year, day }),
+            InputSource::Cached(dir) => match embedded(year, day) {
+                Some(input) => Ok(Cow::Borrowed(input)),
+                None => InputSource::Dir(dir.clone()).load(year, day),
+            },
+            InputSource::File(path) => read_file(path).map(Cow::Owned),
+            InputSource::Stdin => {
+                let mut s = String::new();
+                io::stdin()
+                    .read_to_string(&mut s)
+                    .map_err(|source| InputError::Io {
+                        path: "<stdin>".into(),
+                        source,
+                    })?;
+                Ok(Cow::Owned(s))
+            }
+            InputSource::Dir(dir) => {
+                let path = path_in(dir, year, day);
+                if path.exists() {
+                    read_file(&path).map(Cow::Owned)
+                } else {
+                    Err(InputError::Missing { year, day })
+                }
+            }
+            InputSource::OnDisk { inputs, cache } => {
+                let path = path_in(inputs, year, day);
+                let empty = fs::metadata(&path).map_or(true, |meta| meta.len() == 0);
+                if empty {
+                    InputSource::Dir(cache.clone()).load(year, day)
+                } else {
+                    read_file(&path).map(Cow::Owned)
+                }
+            }
+        }
+    }
+
+    /// The files a day's input could be read from, to watch for changes. The embedded inputs and
+    /// stdin can't change, so they have none.
+    pub fn paths(&self, year: u16, day: u8) -> Vec<PathBuf> {
+        match self {
+            InputSource::Embedded | InputSource::Stdin => vec![],
+            InputSource::Cached(dir) | InputSource::Dir(dir) => vec![path_in(dir, year, day)],
+            InputSource::File(path) => vec![path.clone()],
+            InputSource::OnDisk { inputs, cache } => {
+                vec![path_in(inputs, year, day), path_in(cache, year, day)]
+            }
+        }
+    }
+}
+
+/// Where fetched inputs are kept, laid out like `src/inputs`: `$ADVENT_CACHE` if it's set,
+/// otherwise `advent` in the user's cache directory
+pub fn cache_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("ADVENT_CACHE") {
+        return dir.into();
+    }
+    let base = match (env::var_os("XDG_CACHE_HOME"), env::var_os("HOME")) {
+        (Some(cache), _) => PathBuf::from(cache),
+        (None, Some(home)) => Path::new(&home).join(".cache"),
+        (None, None) => env::temp_dir(),
+    };
+    base.join("advent")
+}
+
+fn read_file(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|source| InputError::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing { year: u16, day: u8 },
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Missing { year, day } => {
+                write!(f, "no input available for {} day {}", year, day)
+            }
+            InputError::Io { path, source } => {
+                write!(f, "couldn't read {}: {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::input_const;
+
+    use super::*;
+
+    #[test]
+    fn embedded_matches_input_const() {
+        assert_eq!(embedded(2021, 1), Some(input_const!(2021, 1)));
+        assert_eq!(embedded(2021, 16), Some(input_const!(2021, 16)));
+        assert_eq!(embedded(2021, 0), None);
+        assert_eq!(embedded(2020, 1), None);
+    }
+
+    #[test]
+    fn load_from_dir_and_file() {
+        let dir = std::env::temp_dir().join(format!("advent-input-test-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("2021")).unwrap();
+        std::fs::write(path_in(&dir, 2021, 3), "101\n010\n").unwrap();
+
+        let source = InputSource::Dir(dir.clone());
+        assert_eq!(source.load(2021, 3).unwrap(), "101\n010\n");
+        assert!(matches!(
+            source.load(2021, 4),
+            Err(InputError::Missing { year: 2021, day: 4 })
+        ));
+        assert!(source.load(2020, 3).is_err());
+
+        std::fs::write(path_in(&dir, 2021, 25), "v>\n").unwrap();
+        let source = InputSource::Cached(dir.clone());
+        assert_eq!(source.load(2021, 3).unwrap(), input_const!(2021, 3));
+        assert_eq!(source.load(2021, 25).unwrap(), "v>\n");
+        assert!(matches!(
+            source.load(2021, 24),
+            Err(InputError::Missing { day: 24, .. })
+        ));
+
+        let source = InputSource::from_arg(path_in(&dir, 2021, 3).to_str().unwrap());
+        assert_eq!(source.load(2022, 17).unwrap(), "101\n010\n");
+
+        let source = InputSource::File(dir.join("nope"));
+        assert!(matches!(source.load(2021, 1), Err(InputError::Io { .. })));
+
+        // a freshly scaffolded day's empty input falls through to the cache
+        let (inputs, cache) = (dir.join("inputs"), dir.clone());
+        std::fs::create_dir_all(inputs.join("2021")).unwrap();
+        std::fs::write(path_in(&inputs, 2021, 25), "").unwrap();
+        std::fs::write(path_in(&inputs, 2021, 3), "111\n").unwrap();
+        let source = InputSource::OnDisk { inputs, cache };
+        assert_eq!(source.load(2021, 25).unwrap(), "v>\n");
+        assert_eq!(source.load(2021, 3).unwrap(), "111\n");
+        assert!(source.load(2021, 24).is_err());
+        assert_eq!(source.paths(2021, 25)[1], path_in(&dir, 2021, 25));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn stdin_arg() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert!(InputSource::Stdin.is_single());
+        assert!(!InputSource::Embedded.is_single());
+    }
+}
@@ -0,0 +1,26 @@
+//! Advent of Code solutions, one module per event.
+//!
+//! Each `yYYYY::dayN` module has a `DayN` type implementing [`solution::Solution`], which parses
+//! the puzzle input and solves both parts, along with the models and algorithms it's built from.
+//! [`runner::DAYS`] lists every day of every year, for running them without knowing their types.
+//! Everything outside the year modules is shared between them.
+
+pub mod alloc;
+pub mod answers;
+pub mod bench;
+pub mod diff;
+pub mod export;
+pub mod fetch;
+pub mod generate;
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+mod utils;
+pub mod visualize;
+pub mod watch;
+pub mod y2021;
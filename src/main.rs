@@ -1,29 +1,767 @@
-#![allow(dead_code)]
-#![feature(test, box_syntax, box_patterns)]
-
-
-extern crate test;
-
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod utils;
-
-fn main() {
-    
-    dbg!(day16::solution1());
-    dbg!(day16::solution2());
+use std::fmt::Display;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use advent::alloc;
+use advent::answers::{self, Answers};
+use advent::bench;
+use advent::bench::history::{self, Tracking};
+use advent::fetch::{self, FetchError, Fetcher};
+use advent::input::{self, InputSource};
+use advent::report::{self, Tally};
+use advent::runner::{self, Part, Solving, DAYS};
+use advent::scaffold;
+use advent::submit::{History, Submitter};
+use advent::visualize::{self, Player};
+use advent::watch::{self, Latest, Watcher};
+use advent::{diff, export, generate};
+use clap::{Args, Parser, Subcommand};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// only counts anything while a run with --alloc is measuring a part
+#[global_allocator]
+static GLOBAL: alloc::Counting = alloc::Counting;
+
+#[derive(Debug, Parser)]
+#[command(name = "advent", about = "Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run the solutions for one day, or for every day
+    Run(RunArgs),
+    /// Check that each day's `fast` implementation agrees with the original, on the real input
+    /// and on random inputs
+    Diff(DiffArgs),
+    /// Time parsing and solving each day, and each `fast` variant, on the puzzle inputs.
+    /// Run a release build for meaningful numbers.
+    Bench(BenchArgs),
+    /// Compare two saved benchmark runs, to see what got faster or slower between them
+    BenchReport(BenchReportArgs),
+    /// Print a random, well-formed input for a day
+    Generate(GenerateArgs),
+    /// Download puzzle inputs into the input cache, unless they're already there. Days without
+    /// an embedded input are read from the cache when running.
+    Fetch(FetchArgs),
+    /// Run a day whenever its input changes, showing how the answers differ from the last run
+    Watch(WatchArgs),
+    /// Draw a grid-based day in the terminal, animating simulations a step at a time
+    Visualize(VisualizeArgs),
+    /// Start a new day: generate its module, an empty input file and its registry entries
+    New(NewArgs),
+    /// Submit a part's answer to the site. Every guess is kept in the input cache, and an answer
+    /// that's already known to be wrong is never submitted again.
+    Submit(SubmitArgs),
+}
+
+#[derive(Debug, Args)]
+struct RunArgs {
+    /// The event year: the one --day is in, or the only one to run with --all [default: the
+    /// latest for --day, every year for --all]
+    #[arg(long)]
+    year: Option<u16>,
+
+    /// The day to run
+    #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u8>,
+
+    /// Only run this part (1 or 2)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Run every registered day at once, and print a table of the answers and timings
+    #[arg(long)]
+    all: bool,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Check answers against this file, instead of the `answers` file next to the inputs
+    #[arg(long)]
+    answers: Option<PathBuf>,
+
+    /// Save answers that aren't in the answers file yet, once you know they're correct
+    #[arg(long)]
+    record: bool,
+
+    /// Print a JSON object per part instead, with the answer, timings and any error
+    #[arg(long)]
+    json: bool,
+
+    /// Also run the `fast` variant of each part that has one (JSON output only)
+    #[arg(long, requires = "json")]
+    fast: bool,
+
+    #[command(flatten)]
+    timeout: TimeoutArgs,
+
+    /// Count the allocations and peak memory of parsing and each part. Parts and days run one
+    /// at a time, so that each count is only its own.
+    #[arg(long)]
+    alloc: bool,
+}
+
+#[derive(Debug, Args)]
+struct DiffArgs {
+    /// Only check this year, and the one --day is in [default: the latest for --day]
+    #[arg(long)]
+    year: Option<u16>,
+
+    /// Only check this day
+    #[arg(long)]
+    day: Option<u8>,
+
+    /// Seed for the random inputs (random if not given)
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// How many random inputs to try for each day
+    #[arg(long, default_value_t = 100)]
+    cases: usize,
+
+    /// The largest random input to generate, in lines, boards, fish, etc.
+    #[arg(long, default_value_t = 20)]
+    size: usize,
+}
+
+#[derive(Debug, Args)]
+struct BenchArgs {
+    /// Only benchmark this year, and the one --day is in [default: the latest for --day]
+    #[arg(long)]
+    year: Option<u16>,
+
+    /// Only benchmark this day
+    #[arg(long)]
+    day: Option<u8>,
+
+    /// Only run benchmarks whose name contains this, e.g. `parse` or `fast`
+    #[arg(long)]
+    filter: Option<String>,
+
+    /// Roughly how long to spend timing each benchmark, in milliseconds
+    #[arg(long, default_value_t = 500)]
+    time: u64,
+
+    /// Run every benchmark in a thread pool of each of these sizes, e.g. `1,2,8`, instead of
+    /// rayon's default pool
+    #[arg(long, value_delimiter = ',')]
+    threads: Vec<usize>,
+
+    /// Print each result as a line of JSON instead of a table
+    #[arg(long)]
+    json: bool,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    #[command(flatten)]
+    history: HistoryArgs,
+
+    /// Add the results to the history, to compare later runs with
+    #[arg(long)]
+    save: bool,
+
+    /// Compare the results with a saved run: `latest`, or the latest run of a commit
+    #[arg(long, value_name = "RUN", num_args = 0..=1, default_missing_value = "latest")]
+    compare: Option<String>,
+}
+
+#[derive(Debug, Args)]
+struct BenchReportArgs {
+    /// The earlier run: `latest`, or the latest run of a commit
+    base: String,
+
+    /// The later run, in the same form
+    #[arg(default_value = "latest")]
+    head: String,
+
+    #[command(flatten)]
+    history: HistoryArgs,
+}
+
+#[derive(Debug, Args)]
+struct GenerateArgs {
+    /// The event year [default: the latest with any solutions]
+    #[arg(long)]
+    year: Option<u16>,
+
+    /// The day whose input format to generate
+    #[arg(long)]
+    day: u8,
+
+    /// Seed for the random input (random if not given)
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// How big an input to generate, in lines, boards, fish, etc.
+    #[arg(long, default_value_t = 20)]
+    size: usize,
+}
+
+#[derive(Debug, Args)]
+struct FetchArgs {
+    /// The event year [default: the latest with any solutions]
+    #[arg(long)]
+    year: Option<u16>,
+
+    /// The day to fetch
+    #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+
+    /// Fetch the input for every registered day of the year
+    #[arg(long)]
+    all: bool,
+
+    /// The `session` cookie from a browser logged in to the site [default: $AOC_SESSION]
+    #[arg(long)]
+    session: Option<String>,
+
+    /// Where to keep inputs [default: $ADVENT_CACHE, or advent in the user's cache]
+    #[arg(long)]
+    cache: Option<PathBuf>,
+
+    /// The site to download from, e.g. a local stand-in for testing
+    #[arg(long, default_value = fetch::DEFAULT_ENDPOINT)]
+    endpoint: String,
+
+    /// The least time to leave between requests, in seconds
+    #[arg(long, default_value_t = fetch::DEFAULT_INTERVAL.as_secs_f64())]
+    interval: f64,
+}
+
+#[derive(Debug, Args)]
+struct WatchArgs {
+    /// The event year [default: the latest with any solutions]
+    #[arg(long)]
+    year: Option<u16>,
+
+    /// The day to run
+    #[arg(long)]
+    day: u8,
+
+    /// Only run this part (1 or 2)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Watch and run this file instead of the day's input, e.g. an example from the puzzle
+    /// [default: the input under src/inputs, or the fetched one]
+    #[arg(long)]
+    input: Option<PathBuf>,
+
+    /// How often to check for changes, in milliseconds
+    #[arg(long, default_value_t = 250)]
+    interval: u64,
+
+    #[command(flatten)]
+    timeout: TimeoutArgs,
+}
+
+#[derive(Debug, Args)]
+struct VisualizeArgs {
+    /// The event year [default: the latest with any solutions]
+    #[arg(long)]
+    year: Option<u16>,
+
+    /// The day to draw: 5, 9, 11, 13 or 15 of 2021
+    #[arg(long)]
+    day: u8,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// How many frames to show a second, or 0 to print them all one after another, which is
+    /// what happens anyway when the output isn't a terminal
+    #[arg(long, default_value_t = 10.0)]
+    fps: f64,
+
+    /// Don't colour the frames, which is also the default when $NO_COLOR is set or the
+    /// output isn't a terminal
+    #[arg(long)]
+    no_color: bool,
+    /// Save the frames as images instead of showing them: a .gif is animated at --fps, and
+    /// .png or .ppm files are numbered when there's more than one frame
+    #[arg(long, value_name = "FILE")]
+    export: Option<PathBuf>,
+
+    /// How many pixels wide each cell is in exported images
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..=64))]
+    scale: u32,
+}
+
+#[derive(Debug, Args)]
+struct NewArgs {
+    /// The event year [default: the latest with any solutions]
+    #[arg(long)]
+    year: Option<u16>,
+
+    /// The day to create
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// The crate to add it to [default: this crate's source]
+    #[arg(long)]
+    root: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct SubmitArgs {
+    /// The event year [default: the latest with any solutions]
+    #[arg(long)]
+    year: Option<u16>,
+
+    /// The day to submit
+    #[arg(long)]
+    day: u8,
+
+    /// The part to submit (1 or 2)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// Submit this instead of running the solution, e.g. the letters in a picture
+    #[arg(long)]
+    answer: Option<String>,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Submit even if earlier guesses show the answer is too high or too low
+    #[arg(long)]
+    force: bool,
+
+    /// The `session` cookie from a browser logged in to the site [default: $AOC_SESSION]
+    #[arg(long)]
+    session: Option<String>,
+
+    /// Where inputs and guesses are kept [default: $ADVENT_CACHE, or advent in the user's
+    /// cache]
+    #[arg(long)]
+    cache: Option<PathBuf>,
+
+    /// The site to submit to, e.g. a local stand-in for testing
+    #[arg(long, default_value = fetch::DEFAULT_ENDPOINT)]
+    endpoint: String,
+}
+
+#[derive(Debug, Args)]
+struct InputArgs {
+    /// Read the puzzle input from this file instead of the embedded or fetched one (`-` for
+    /// stdin)
+    #[arg(long, conflicts_with = "inputs")]
+    input: Option<String>,
+
+    /// Read puzzle inputs from a directory laid out like `src/inputs`, e.g. `2021/input1`
+    #[arg(long)]
+    inputs: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct TimeoutArgs {
+    /// Give up on a part after this many seconds, and report it as timed out (0 for no limit)
+    #[arg(long, default_value_t = runner::DEFAULT_TIMEOUT.as_secs_f64())]
+    timeout: f64,
+}
+
+#[derive(Debug, Args)]
+struct HistoryArgs {
+    /// Where benchmark runs are saved [default: bench-history.jsonl in the crate root]
+    #[arg(long)]
+    history: Option<PathBuf>,
+
+    /// Ignore changes smaller than this percentage, as noise
+    #[arg(long, default_value_t = 5.0)]
+    threshold: f64,
+}
+
+impl HistoryArgs {
+    fn path(&self) -> PathBuf {
+        self.history.clone().unwrap_or_else(history::default_path)
+    }
+}
+
+impl TimeoutArgs {
+    fn limit(&self) -> Result<Option<Duration>, String> {
+        match Duration::try_from_secs_f64(self.timeout) {
+            Ok(limit) if limit.is_zero() => Ok(None),
+            Ok(limit) => Ok(Some(limit)),
+            Err(_) => Err("--timeout must be a number of seconds".to_string()),
+        }
+    }
+}
+
+impl InputArgs {
+    fn source(&self) -> InputSource {
+        match (&self.input, &self.inputs) {
+            (Some(input), _) => InputSource::from_arg(input),
+            (None, Some(dir)) => InputSource::Dir(dir.clone()),
+            (None, None) => InputSource::Cached(input::cache_dir()),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::Run(args) => run(args),
+        Command::Diff(args) => diff(args),
+        Command::Bench(args) => bench(args),
+        Command::BenchReport(args) => bench_report(args),
+        Command::Generate(args) => generate(args),
+        Command::Fetch(args) => fetch(args),
+        Command::Watch(args) => watch(args),
+        Command::Visualize(args) => visualize(args),
+        Command::New(args) => new(args),
+        Command::Submit(args) => submit(args),
+    };
+    // e.g. stdout was closed early
+    result.unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        ExitCode::FAILURE
+    })
+}
+
+/// Report a problem with the arguments
+fn usage(message: impl Display) -> io::Result<ExitCode> {
+    eprintln!("{}", message);
+    Ok(ExitCode::from(2))
+}
+
+/// Report a problem that stopped the command
+fn failure(message: impl Display) -> io::Result<ExitCode> {
+    eprintln!("{}", message);
+    Ok(ExitCode::FAILURE)
+}
+
+fn exit_code(succeeded: bool) -> io::Result<ExitCode> {
+    Ok(if succeeded {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+/// Just `part`, or both parts if it's not given
+fn parts(part: Option<u8>) -> Vec<Part> {
+    match part.and_then(Part::from_number) {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    }
+}
+
+fn run(args: RunArgs) -> io::Result<ExitCode> {
+    let days = match runner::select_days(args.year, args.day) {
+        Ok(days) => days,
+        Err(e) => return usage(e),
+    };
+    let limit = match args.timeout.limit() {
+        Ok(limit) => limit,
+        Err(e) => return usage(e),
+    };
+    let solving = Solving {
+        limit,
+        profile: args.alloc,
+    };
+    let parts = parts(args.part);
+
+    let source = args.input.source();
+    if source.is_single() && days.len() > 1 {
+        return usage("a single input file can only be used with --day");
+    }
+
+    let answers_path = args.answers.or_else(|| answers::default_path(&source));
+    let mut answers = match &answers_path {
+        Some(path) => match Answers::load(path) {
+            Ok(answers) => Some(answers),
+            Err(e) => return usage(e),
+        },
+        None if args.record => {
+            return usage("--record needs --answers when reading a single input file");
+        }
+        None => None,
+    };
+
+    let mut tally = Tally::new(answers.as_mut(), args.record);
+    let mut out = io::stdout().lock();
+    if args.json {
+        report::run_json(
+            &mut out, &days, &parts, &source, solving, args.fast, &mut tally,
+        )?;
+    } else if args.all {
+        report::run_all(&mut out, &days, &parts, &source, solving, &mut tally)?;
+    } else {
+        report::run(&mut out, &days, &parts, &source, solving, &mut tally)?;
+    }
+    let failed = tally.failed();
+
+    if let (true, Some(path), Some(answers)) = (args.record, answers_path, answers) {
+        if let Err(e) = answers.save(&path) {
+            return failure(e);
+        }
+    }
+    exit_code(!failed)
+}
+
+fn diff(args: DiffArgs) -> io::Result<ExitCode> {
+    let pairs = diff::pairs(args.year, args.day);
+    if pairs.is_empty() {
+        return usage("no fast implementations to compare");
+    }
+
+    let seed = args.seed.unwrap_or_else(generate::random_seed);
+    let mut out = io::stdout().lock();
+    writeln!(out, "seed {}", seed)?;
+    let agreed = diff::check_all(&mut out, &pairs, seed, args.cases, args.size)?;
+    exit_code(agreed)
+}
+
+fn bench(args: BenchArgs) -> io::Result<ExitCode> {
+    let days = match runner::select_days(args.year, args.day) {
+        Ok(days) => days,
+        Err(e) => return usage(e),
+    };
+    let source = args.input.source();
+    if source.is_single() && days.len() > 1 {
+        return usage("a single input file can only be used with --day");
+    }
+    if cfg!(debug_assertions) {
+        eprintln!("warning: benchmarking a debug build, use --release for real numbers");
+    }
+
+    let tracking = match Tracking::new(
+        args.history.path(),
+        args.compare.as_deref(),
+        args.history.threshold,
+        args.save,
+    ) {
+        Ok(tracking) => tracking,
+        Err(e) => return usage(e),
+    };
+
+    let threads: Vec<_> = match args.threads.as_slice() {
+        [] => vec![None],
+        threads => threads.iter().copied().map(Some).collect(),
+    };
+    let budget = Duration::from_millis(args.time);
+    let filter = args.filter.as_deref();
+    let mut out = io::stdout().lock();
+    let (measurements, succeeded) = bench::run_days(
+        &mut out, &days, filter, &source, budget, &threads, args.json,
+    )?;
+    let saved = tracking.finish(&mut out, measurements)?;
+    exit_code(succeeded && saved)
+}
+
+fn bench_report(args: BenchReportArgs) -> io::Result<ExitCode> {
+    let path = args.history.path();
+    let (base, head) = match history::load_run(&path, &args.base)
+        .and_then(|base| Ok((base, history::load_run(&path, &args.head)?)))
+    {
+        Ok(runs) => runs,
+        Err(e) => return usage(e),
+    };
+
+    let mut out = io::stdout().lock();
+    writeln!(
+        out,
+        "{} compared with {}:",
+        head.describe(),
+        base.describe()
+    )?;
+    let comparisons = history::compare(&base, &head.measurements, args.history.threshold);
+    history::report(&mut out, &comparisons)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn generate(args: GenerateArgs) -> io::Result<ExitCode> {
+    let year = args.year.unwrap_or_else(runner::latest_year);
+    let Some(generate) = generate::generator(year, args.day) else {
+        return usage(format!(
+            "no input format known for {} day {}",
+            year, args.day
+        ));
+    };
+    let seed = args.seed.unwrap_or_else(generate::random_seed);
+    eprintln!("seed {}", seed);
+    let input = generate(&mut StdRng::seed_from_u64(seed), args.size);
+    io::stdout().lock().write_all(input.as_bytes())?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn fetch(args: FetchArgs) -> io::Result<ExitCode> {
+    let year = args.year.unwrap_or_else(runner::latest_year);
+    let days: Vec<u8> = match args.day {
+        Some(day) => vec![day],
+        None => DAYS
+            .iter()
+            .filter(|day| day.year == year)
+            .map(|day| day.number)
+            .collect(),
+    };
+    let session = args.session.or_else(|| std::env::var("AOC_SESSION").ok());
+    let cache = args.cache.unwrap_or_else(input::cache_dir);
+    let Ok(interval) = Duration::try_from_secs_f64(args.interval) else {
+        return usage("--interval must be a number of seconds");
+    };
+    let fetcher = Fetcher::new(fetch::Ureq::new(), session, cache)
+        .endpoint(args.endpoint)
+        .interval(interval);
+
+    let fetched = fetcher.fetch_all(&mut io::stdout().lock(), year, &days)?;
+    exit_code(fetched)
+}
+
+fn watch(args: WatchArgs) -> io::Result<ExitCode> {
+    let year = args.year.unwrap_or_else(runner::latest_year);
+    let Some(solver) = runner::find_day(year, args.day) else {
+        return usage(format!(
+            "no solution registered for {} day {}",
+            year, args.day
+        ));
+    };
+    let limit = match args.timeout.limit() {
+        Ok(limit) => limit,
+        Err(e) => return usage(e),
+    };
+    let parts = parts(args.part);
+    // read from disk every time rather than using the embedded input, which can't change
+    let source = match args.input {
+        Some(path) => InputSource::File(path),
+        None => InputSource::OnDisk {
+            inputs: input::source_dir(),
+            cache: input::cache_dir(),
+        },
+    };
+
+    let paths = source.paths(year, args.day);
+    let watching: Vec<_> = paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect();
+    let mut watcher = Watcher::new(paths);
+    let mut latest = Latest::default();
+    loop {
+        let mut out = io::stdout().lock();
+        watch::run(&mut out, solver, &parts, &source, limit, &mut latest)?;
+        writeln!(out, "\nwatching {} for changes", watching.join(" and "))?;
+        // don't hold stdout while waiting
+        drop(out);
+        watcher.wait(Duration::from_millis(args.interval));
+        println!();
+    }
+}
+
+fn visualize(args: VisualizeArgs) -> io::Result<ExitCode> {
+    let (year, day) = (args.year.unwrap_or_else(runner::latest_year), args.day);
+    let Some(visualization) = visualize::visualization(year, day) else {
+        return usage(format!("no visualization for {} day {}", year, day));
+    };
+    let input = match args.input.source().load(year, day) {
+        Ok(input) => input,
+        Err(e) => return failure(e),
+    };
+    let frames = match visualization(&input) {
+        Ok(frames) => frames,
+        Err(e) => return failure(format!("{} day {}: invalid input: {}", year, day, e)),
+    };
+
+    if let Some(path) = args.export {
+        // printing every frame at once has no speed, so animate those at the default
+        let fps = if args.fps > 0.0 { args.fps } else { 10.0 };
+        let paths = match export::export(frames, &path, args.scale as usize, fps) {
+            Ok(paths) => paths,
+            Err(e) => return failure(e),
+        };
+        let mut out = io::stdout().lock();
+        for path in paths {
+            writeln!(out, "wrote {}", path.display())?;
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let stdout = io::stdout();
+    let terminal = stdout.is_terminal();
+    let color = terminal && !args.no_color && std::env::var_os("NO_COLOR").is_none();
+    let mut player = Player::new(stdout.lock()).color(color);
+    if terminal && args.fps > 0.0 {
+        player = player.fps(args.fps);
+    }
+    match player.play(frames) {
+        Ok(_) => Ok(ExitCode::SUCCESS),
+        Err(e) => failure(e),
+    }
+}
+
+fn new(args: NewArgs) -> io::Result<ExitCode> {
+    let year = args.year.unwrap_or_else(runner::latest_year);
+    let root = args
+        .root
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")));
+    let scaffolded = match scaffold::scaffold(&root, year, args.day) {
+        Ok(scaffolded) => scaffolded,
+        Err(e) => return failure(e),
+    };
+
+    let mut out = io::stdout().lock();
+    writeln!(out, "created {}", scaffolded.module.display())?;
+    writeln!(out, "created {}", scaffolded.input.display())?;
+    for path in scaffolded.registered {
+        writeln!(
+            out,
+            "registered {} day {} in {}",
+            year,
+            args.day,
+            path.display()
+        )?;
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn submit(args: SubmitArgs) -> io::Result<ExitCode> {
+    let (year, day) = (args.year.unwrap_or_else(runner::latest_year), args.day);
+    let part = Part::from_number(args.part).expect("clap checks the part");
+    let Some(session) = args.session.or_else(|| std::env::var("AOC_SESSION").ok()) else {
+        return usage(FetchError::NoSession);
+    };
+    let cache = args.cache.unwrap_or_else(input::cache_dir);
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => match runner::solve(year, day, part, &args.input.source()) {
+            Ok(answer) => answer,
+            Err(e) => {
+                return failure(format!(
+                    "{} day {} part {}: {}",
+                    year,
+                    day,
+                    part.number(),
+                    e
+                ))
+            }
+        },
+    };
+    if answer.contains('\n') {
+        eprintln!(
+            "{} day {} part {} has a multi-line answer:",
+            year,
+            day,
+            part.number()
+        );
+        eprintln!("{}", answer.trim_end());
+        return usage("read it and pass it with --answer");
+    }
+
+    let mut history = match History::load(&cache, year, day) {
+        Ok(history) => history,
+        Err(e) => return failure(e),
+    };
+    let submitter = Submitter::new(fetch::Ureq::new(), session).endpoint(args.endpoint);
+    let mut out = io::stdout().lock();
+    match submitter.submit_checked(&mut out, &mut history, part, &answer, args.force) {
+        Ok(correct) => exit_code(correct),
+        Err(e) => failure(e),
+    }
 }
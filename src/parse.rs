@@ -0,0 +1,345 @@
+//! Parsing puzzle inputs with errors that point at the problem. A [`Span`] is a piece of the
+//! input that knows where it came from; the functions here take the common puzzle formats apart
+//! into spans: comma-separated numbers, `x,y` pairs, `key -> value` rules, blank-line-separated
+//! sections and single-line inputs. Grids of digits are [`Grid::parse_digits`].
+//!
+//! [`Grid::parse_digits`]: crate::grid::Grid::parse_digits
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Something in the puzzle input that wasn't what we expected, and where it was
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in characters
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    pub fn new(
+        line: usize,
+        column: usize,
+        expected: impl Into<String>,
+        found: impl Into<String>,
+    ) -> Self {
+        Self {
+            line,
+            column,
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+
+    /// The input ran out while we still expected something
+    pub fn end_of_input(input: &str, expected: impl Into<String>) -> Self {
+        Self::new(input.lines().count() + 1, 1, expected, "end of input")
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A piece of the puzzle input that remembers where it came from, so errors can point at it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    text: &'a str,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Span<'a> {
+    /// Every line of the input, numbered from 1
+    pub fn lines(input: &'a str) -> impl Iterator<Item = Span<'a>> {
+        input.lines().enumerate().map(|(i, text)| Span {
+            text,
+            line: i + 1,
+            column: 1,
+        })
+    }
+
+    /// The groups of lines separated by blank lines, e.g. the boards in a game of bingo. Any
+    /// number of blank lines separates two groups, so no group is empty.
+    pub fn sections(input: &'a str) -> impl Iterator<Item = Vec<Span<'a>>> {
+        let mut lines = Span::lines(input).peekable();
+        std::iter::from_fn(move || {
+            while lines.next_if(Span::is_blank).is_some() {}
+            let section: Vec<_> =
+                std::iter::from_fn(|| lines.next_if(|line| !line.is_blank())).collect();
+            (!section.is_empty()).then_some(section)
+        })
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Whether there's nothing but whitespace
+    pub fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    /// The span covering `piece`, which must be a substring of this span's text
+    fn sub(&self, piece: &'a str) -> Span<'a> {
+        let offset = piece.as_ptr() as usize - self.text.as_ptr() as usize;
+        Span {
+            text: piece,
+            line: self.line,
+            column: self.column + self.text[..offset].chars().count(),
+        }
+    }
+
+    pub fn trim(self) -> Span<'a> {
+        self.sub(self.text.trim())
+    }
+
+    pub fn split(self, separator: &'a str) -> impl Iterator<Item = Span<'a>> {
+        self.text.split(separator).map(move |piece| self.sub(piece))
+    }
+
+    pub fn split_whitespace(self) -> impl Iterator<Item = Span<'a>> {
+        self.text
+            .split_whitespace()
+            .map(move |piece| self.sub(piece))
+    }
+
+    pub fn split_once(self, separator: &str) -> Option<(Span<'a>, Span<'a>)> {
+        let (before, after) = self.text.split_once(separator)?;
+        Some((self.sub(before), self.sub(after)))
+    }
+
+    /// Each character, as a single-character span
+    pub fn chars(self) -> impl Iterator<Item = (Span<'a>, char)> {
+        self.text
+            .char_indices()
+            .map(move |(i, c)| (self.sub(&self.text[i..i + c.len_utf8()]), c))
+    }
+
+    pub fn parse<T: FromStr>(self, expected: &str) -> Result<T, ParseError> {
+        self.text.parse().map_err(|_| self.error(expected))
+    }
+
+    /// An error pointing at the start of this span, reporting its text as what was found
+    pub fn error(self, expected: impl Into<String>) -> ParseError {
+        let found = if self.text.is_empty() {
+            "nothing".to_string()
+        } else {
+            format!("{:?}", self.text)
+        };
+        ParseError::new(self.line, self.column, expected, found)
+    }
+
+    /// An error pointing just past the end of this span
+    pub fn end_error(self, expected: impl Into<String>) -> ParseError {
+        ParseError::new(
+            self.line,
+            self.column + self.text.chars().count(),
+            expected,
+            "end of line",
+        )
+    }
+}
+
+/// The next piece from `pieces`, or an error at the end of `whole` if there isn't one
+pub fn next<'a>(
+    pieces: &mut impl Iterator<Item = Span<'a>>,
+    whole: Span<'a>,
+    expected: &str,
+) -> Result<Span<'a>, ParseError> {
+    pieces.next().ok_or_else(|| whole.end_error(expected))
+}
+
+/// Error if there is anything left in `pieces`
+pub fn end<'a>(pieces: &mut impl Iterator<Item = Span<'a>>) -> Result<(), ParseError> {
+    match pieces.next() {
+        Some(extra) => Err(extra.error("end of line")),
+        None => Ok(()),
+    }
+}
+
+/// The only non-blank line of the input, trimmed, for puzzles whose input is one long line
+pub fn single_line<'a>(input: &'a str, expected: &str) -> Result<Span<'a>, ParseError> {
+    let mut lines = Span::lines(input).filter(|line| !line.is_blank());
+    let line = lines
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(input, expected))?;
+    match lines.next() {
+        Some(extra) => Err(extra.trim().error("end of input")),
+        None => Ok(line.trim()),
+    }
+}
+
+/// The two sides of the first `separator`, like the `x` and `y` of `x,y`. Without a separator,
+/// the error points past the end, where the separator and the second side should have been.
+pub fn pair<'a>(
+    s: Span<'a>,
+    separator: &str,
+    expected: &str,
+) -> Result<(Span<'a>, Span<'a>), ParseError> {
+    s.split_once(separator).ok_or_else(|| s.end_error(expected))
+}
+
+/// The key and value of a line like `AB -> C`, either side of `separator`. A line without one
+/// isn't a rule at all, so the error points at the whole line.
+pub fn rule<'a>(
+    s: Span<'a>,
+    separator: &str,
+    expected: &str,
+) -> Result<(Span<'a>, Span<'a>), ParseError> {
+    s.split_once(separator).ok_or_else(|| s.error(expected))
+}
+
+/// Each of the `separator`-separated items, trimmed and then parsed by `item`
+pub fn list<'a, T>(
+    s: Span<'a>,
+    separator: &'a str,
+    item: impl FnMut(Span<'a>) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    s.split(separator).map(Span::trim).map(item).collect()
+}
+
+/// A list of numbers like `3,4,3,1,2`
+pub fn numbers<'a, T: FromStr>(
+    s: Span<'a>,
+    separator: &'a str,
+    expected: &str,
+) -> Result<Vec<T>, ParseError> {
+    list(s, separator, |number| number.parse(expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_are_numbered_from_one() {
+        let lines: Vec<_> = Span::lines("a\nbc\n\nd").collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!((lines[0].line(), lines[0].column()), (1, 1));
+        assert_eq!((lines[3].line(), lines[3].as_str()), (4, "d"));
+        assert!(lines[2].is_empty());
+    }
+
+    #[test]
+    fn splitting_tracks_columns() {
+        let line = Span::lines("x\n  12,345 -> 6").nth(1).unwrap();
+        let words: Vec<_> = line.split_whitespace().collect();
+        assert_eq!(words[0].as_str(), "12,345");
+        assert_eq!(words[0].column(), 3);
+        assert_eq!(words[2].column(), 13);
+
+        let numbers: Vec<_> = words[0].split(",").collect();
+        assert_eq!(numbers[1].as_str(), "345");
+        assert_eq!(numbers[1].column(), 6);
+        assert_eq!(numbers[1].line(), 2);
+
+        let (left, right) = line.trim().split_once(" -> ").unwrap();
+        assert_eq!((left.as_str(), left.column()), ("12,345", 3));
+        assert_eq!((right.as_str(), right.column()), ("6", 13));
+
+        let (c, _) = right.chars().next().unwrap();
+        assert_eq!(c.column(), 13);
+    }
+
+    #[test]
+    fn errors_point_at_the_problem() {
+        let line = Span::lines("1,2,x").next().unwrap();
+        let bad = line.split(",").nth(2).unwrap();
+        assert_eq!(
+            bad.parse::<u32>("a number").unwrap_err().to_string(),
+            r#"line 1, column 5: expected a number, found "x""#
+        );
+        assert_eq!(
+            line.end_error("a comma").to_string(),
+            "line 1, column 6: expected a comma, found end of line"
+        );
+        assert_eq!(
+            ParseError::end_of_input("a\nb\n", "a board"),
+            ParseError::new(3, 1, "a board", "end of input")
+        );
+    }
+
+    #[test]
+    fn next_and_end() {
+        let line = Span::lines("a b").next().unwrap();
+        let mut words = line.split_whitespace();
+        assert_eq!(next(&mut words, line, "a word").unwrap().as_str(), "a");
+        assert!(end(&mut words).is_err());
+        assert_eq!(next(&mut words, line, "a word").unwrap_err().column, 4);
+        assert!(end(&mut words).is_ok());
+    }
+
+    #[test]
+    fn sections_are_separated_by_blank_lines() {
+        let sections: Vec<Vec<_>> = Span::sections("\na\nb\n\n  \n\nc\n\n")
+            .map(|section| section.iter().map(|line| line.as_str()).collect())
+            .collect();
+        assert_eq!(sections, [vec!["a", "b"], vec!["c"]]);
+        assert_eq!(Span::sections("").count(), 0);
+
+        let c = Span::sections("a\n\nc").nth(1).unwrap()[0];
+        assert_eq!((c.line(), c.column()), (3, 1));
+    }
+
+    #[test]
+    fn single_lines() {
+        let line = single_line("\n  3,4,5 \n\n", "a list").unwrap();
+        assert_eq!((line.as_str(), line.line(), line.column()), ("3,4,5", 2, 3));
+
+        let err = single_line("\n \n", "a list").unwrap_err();
+        assert_eq!(err, ParseError::new(3, 1, "a list", "end of input"));
+        let err = single_line("1,2\n\n 3", "a list").unwrap_err();
+        assert_eq!(err, ParseError::new(3, 2, "end of input", "\"3\""));
+    }
+
+    #[test]
+    fn pairs_and_rules() {
+        let line = Span::lines("12,34 -> 5").next().unwrap();
+        let (key, value) = rule(line, " -> ", "a rule").unwrap();
+        let (x, y) = pair(key, ",", "a comma").unwrap();
+        assert_eq!((x.as_str(), y.as_str(), y.column()), ("12", "34", 4));
+        assert_eq!((value.as_str(), value.column()), ("5", 10));
+
+        let err = pair(value, ",", "a comma").unwrap_err();
+        assert_eq!((err.column, err.found.as_str()), (11, "end of line"));
+        let err = rule(key, " -> ", "a rule").unwrap_err();
+        assert_eq!((err.column, err.found.as_str()), (1, "\"12,34\""));
+    }
+
+    #[test]
+    fn lists() {
+        let line = Span::lines("3, 4,x").next().unwrap();
+        let err = numbers::<u8>(line, ",", "a number").unwrap_err();
+        assert_eq!((err.column, err.found.as_str()), (6, "\"x\""));
+        let words = list(line, ",", |word| Ok(word.as_str())).unwrap();
+        assert_eq!(words, ["3", "4", "x"]);
+
+        let line = Span::lines("1,,2").next().unwrap();
+        let err = numbers::<u8>(line, ",", "a number").unwrap_err();
+        assert_eq!((err.column, err.found.as_str()), (3, "nothing"));
+    }
+}
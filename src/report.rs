@@ -0,0 +1,509 @@
+//! Running days for the `run` command and reporting how they went: a line per part, a table of
+//! every day, or a line of JSON per part, with each answer checked against the [`Answers`] file
+//! if there is one.
+
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crate::alloc::Usage;
+use crate::answers::{Answers, Verdict};
+use crate::diff;
+use crate::input::InputSource;
+use crate::runner::{Day, Part, Record, Solving, Variant};
+
+/// Run each day, writing each part's answer and how long it took, then the tally of answers
+pub fn run(
+    out: &mut impl Write,
+    days: &[&Day],
+    parts: &[Part],
+    source: &InputSource,
+    solving: Solving,
+    tally: &mut Tally,
+) -> io::Result<()> {
+    for day in days {
+        let input = match source.load(day.year, day.number) {
+            Ok(input) => input,
+            Err(e) => {
+                tally.failed = true;
+                writeln!(out, "{}: {}", day, e)?;
+                continue;
+            }
+        };
+
+        let run = solving.run_day(day, &input, parts);
+        let outcomes = match run.result {
+            Ok(outcomes) => outcomes,
+            Err(failure) => {
+                tally.failed = true;
+                writeln!(out, "{}: {}", day, failure)?;
+                continue;
+            }
+        };
+
+        writeln!(
+            out,
+            "{}: parsed in {}",
+            day,
+            took(run.parse_time, run.parse_usage)
+        )?;
+        for outcome in outcomes {
+            let took = took(outcome.elapsed, outcome.usage);
+            match outcome.result {
+                Ok(answer) => {
+                    let verdict = match tally.check(day, outcome.part, &answer) {
+                        Some(Checked::Fail(expected)) => {
+                            format!(" [FAIL, expected {}]", on_own_line(&expected))
+                        }
+                        Some(checked) => format!(" [{}]", checked.label()),
+                        None => String::new(),
+                    };
+                    writeln!(
+                        out,
+                        "{} part {}: {} ({}){}",
+                        day,
+                        outcome.part.number(),
+                        on_own_line(&answer),
+                        took,
+                        verdict
+                    )?;
+                }
+                Err(failure) => {
+                    tally.failed = true;
+                    writeln!(
+                        out,
+                        "{} part {}: {} ({})",
+                        day,
+                        outcome.part.number(),
+                        failure,
+                        took
+                    )?;
+                }
+            }
+        }
+    }
+
+    tally.summary(out)
+}
+
+/// Run every day at once, then write a table of the answers and how long each took, and the
+/// tally of answers
+pub fn run_all(
+    out: &mut impl Write,
+    days: &[&Day],
+    parts: &[Part],
+    source: &InputSource,
+    solving: Solving,
+    tally: &mut Tally,
+) -> io::Result<()> {
+    let inputs: Vec<_> = days
+        .iter()
+        .map(|day| source.load(day.year, day.number))
+        .collect();
+    let loaded: Vec<_> = days
+        .iter()
+        .zip(&inputs)
+        .filter_map(|(day, input)| Some((*day, input.as_deref().ok()?)))
+        .collect();
+
+    let start = Instant::now();
+    let mut runs = solving.run_days(&loaded, parts).into_iter();
+    let wall_time = start.elapsed();
+
+    let mut rows = vec![];
+    // multi-line answers don't fit in the table, so they go underneath it
+    let mut below = vec![];
+    for (day, input) in days.iter().zip(&inputs) {
+        if let Err(e) = input {
+            tally.failed = true;
+            rows.push(Row::failed(day, e.to_string(), None));
+            continue;
+        }
+
+        let run = runs.next().expect("a run for every loaded input");
+        let outcomes = match run.result {
+            Ok(outcomes) => outcomes,
+            Err(failure) => {
+                tally.failed = true;
+                rows.push(Row::failed(day, failure.to_string(), Some(run.parse_time)));
+                continue;
+            }
+        };
+
+        for (i, outcome) in outcomes.into_iter().enumerate() {
+            let mut row = Row {
+                year: run.year,
+                day: run.day,
+                part: Some(outcome.part),
+                parse: (i == 0).then_some(run.parse_time),
+                solve: Some(outcome.elapsed),
+                memory: outcome.usage,
+                ..Row::default()
+            };
+            let label = format!("{} part {}", day, outcome.part.number());
+            match outcome.result {
+                Ok(answer) => {
+                    row.verdict = match tally.check(day, outcome.part, &answer) {
+                        Some(Checked::Fail(expected)) if expected.contains('\n') => {
+                            below.push(format!("{}, expected:\n{}", label, expected.trim_end()));
+                            "FAIL, expected answer below".to_string()
+                        }
+                        Some(Checked::Fail(expected)) => format!("FAIL, expected {}", expected),
+                        Some(checked) => checked.label().to_string(),
+                        None => String::new(),
+                    };
+                    row.answer = if answer.contains('\n') {
+                        below.push(format!("{}:\n{}", label, answer.trim_end()));
+                        "(below)".to_string()
+                    } else {
+                        answer
+                    };
+                }
+                Err(failure) => {
+                    tally.failed = true;
+                    row.answer = failure.to_string();
+                }
+            }
+            rows.push(row);
+        }
+    }
+
+    write!(out, "{}", table(&rows))?;
+    for answer in below {
+        writeln!(out, "\n{}", answer)?;
+    }
+    let busy_time: Duration = rows
+        .iter()
+        .flat_map(|row| row.parse.into_iter().chain(row.solve))
+        .sum();
+    writeln!(
+        out,
+        "\n{} days in {:.2?} ({:.2?} spent parsing and solving)",
+        days.len(),
+        wall_time,
+        busy_time
+    )?;
+
+    tally.summary(out)
+}
+
+/// Run each day at once, writing a JSON record of each part as a line, in order. With `fast`,
+/// days with `fast` variants get a record for each of those too. The verdicts are in the
+/// records, so there's no tally at the end.
+pub fn run_json(
+    out: &mut impl Write,
+    days: &[&Day],
+    parts: &[Part],
+    source: &InputSource,
+    solving: Solving,
+    fast: bool,
+    tally: &mut Tally,
+) -> io::Result<()> {
+    let inputs: Vec<_> = days
+        .iter()
+        .map(|day| source.load(day.year, day.number))
+        .collect();
+    let loaded: Vec<_> = days
+        .iter()
+        .zip(&inputs)
+        .filter_map(|(day, input)| Some((*day, input.as_deref().ok()?)))
+        .collect();
+    let mut runs = solving.run_days(&loaded, parts).into_iter();
+
+    for (day, input) in days.iter().zip(&inputs) {
+        let input = match input {
+            Ok(input) => input,
+            Err(e) => {
+                tally.failed = true;
+                let error = e.to_string();
+                let line = Record::error(day.year, day.number, None, Variant::Slow, error);
+                writeln!(out, "{}", line.to_json())?;
+                continue;
+            }
+        };
+
+        let mut lines = runs.next().expect("a run for every loaded input").records();
+        if fast {
+            let pairs = diff::PAIRS
+                .iter()
+                .filter(|pair| (pair.year, pair.day) == (day.year, day.number))
+                .filter(|pair| parts.contains(&pair.part));
+            lines.extend(pairs.map(|pair| pair.record(input)));
+        }
+
+        for mut line in lines {
+            tally.failed |= line.error.is_some();
+            if let (Some(answer), Some(part)) =
+                (&line.answer, line.part.and_then(Part::from_number))
+            {
+                // only the original solution's answers are trusted enough to record
+                let checked = if line.variant == Variant::Slow {
+                    tally.check(day, part, answer)
+                } else {
+                    tally.check_only(day, part, answer)
+                };
+                match checked {
+                    Some(Checked::Fail(expected)) => {
+                        line.verdict = Some("fail".to_string());
+                        line.expected = Some(expected);
+                    }
+                    Some(checked) => line.verdict = Some(checked.label().to_string()),
+                    None => {}
+                }
+            }
+            writeln!(out, "{}", line.to_json())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// One line of the `run --all` table
+#[derive(Debug, Default)]
+pub struct Row {
+    pub year: u16,
+    pub day: u8,
+    pub part: Option<Part>,
+    /// The answer, or what went wrong
+    pub answer: String,
+    pub parse: Option<Duration>,
+    pub solve: Option<Duration>,
+    /// What solving allocated, with --alloc
+    pub memory: Option<Usage>,
+    pub verdict: String,
+}
+
+impl Row {
+    /// A day that couldn't be solved at all
+    pub fn failed(day: &Day, message: String, parse: Option<Duration>) -> Self {
+        Self {
+            year: day.year,
+            day: day.number,
+            answer: message,
+            parse,
+            ..Self::default()
+        }
+    }
+}
+
+/// The rows lined up under a header, with a memory column only if any row has a count
+pub fn table(rows: &[Row]) -> String {
+    let time = |t: Option<Duration>| t.map_or(String::new(), |t| format!("{:.2?}", t));
+    let cells: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            [
+                row.year.to_string(),
+                row.day.to_string(),
+                row.part
+                    .map_or(String::new(), |part| part.number().to_string()),
+                row.answer.clone(),
+                time(row.parse),
+                time(row.solve),
+                row.memory.map_or(String::new(), |usage| usage.to_string()),
+                row.verdict.clone(),
+            ]
+        })
+        .collect();
+    // memory is only counted with --alloc
+    let memory = rows.iter().any(|row| row.memory.is_some());
+
+    let header = [
+        "year", "day", "part", "answer", "parse", "solve", "memory", "",
+    ];
+    let mut widths = header.map(str::len);
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: [&str; 8]| {
+        let line: Vec<_> = cells
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .filter(|&(i, _)| memory || i != 6)
+            .map(|(i, (cell, width))| match i {
+                // answers, memory and verdicts are text, everything else lines up on the right
+                3 | 6 | 7 => format!("{:<1$}", cell, width),
+                _ => format!("{:>1$}", cell, width),
+            })
+            .collect();
+        format!("{}\n", line.join("  ").trim_end())
+    };
+    let mut table = line(header);
+    for row in &cells {
+        table.push_str(&line(row.each_ref().map(String::as_str)));
+    }
+    table
+}
+
+/// How an answer compared with the answers file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Checked {
+    Pass,
+    Fail(String),
+    Unknown,
+    Recorded,
+}
+
+impl Checked {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Checked::Pass => "pass",
+            Checked::Fail(_) => "FAIL",
+            Checked::Unknown => "unknown",
+            Checked::Recorded => "recorded",
+        }
+    }
+}
+
+/// Checks answers against the answers file, if there is one, counting how they compared and
+/// whether anything went wrong
+#[derive(Debug, Default)]
+pub struct Tally<'a> {
+    answers: Option<&'a mut Answers>,
+    /// Whether to record answers that aren't in the file yet
+    record: bool,
+    failed: bool,
+    passed: usize,
+    wrong: usize,
+    unknown: usize,
+    recorded: usize,
+}
+
+impl<'a> Tally<'a> {
+    pub fn new(answers: Option<&'a mut Answers>, record: bool) -> Self {
+        Self {
+            answers,
+            record,
+            ..Self::default()
+        }
+    }
+
+    /// Whether any day or part failed, or got the wrong answer
+    pub fn failed(&self) -> bool {
+        self.failed
+    }
+
+    /// Check `answer` against the answers, if there are any, recording it if it's unknown and
+    /// answers are being recorded
+    pub fn check(&mut self, day: &Day, part: Part, answer: &str) -> Option<Checked> {
+        self.compare(day, part, answer, self.record)
+    }
+
+    /// Like [`check`](Self::check), but never record the answer
+    pub fn check_only(&mut self, day: &Day, part: Part, answer: &str) -> Option<Checked> {
+        self.compare(day, part, answer, false)
+    }
+
+    fn compare(&mut self, day: &Day, part: Part, answer: &str, record: bool) -> Option<Checked> {
+        let answers = self.answers.as_deref_mut()?;
+        let (year, day) = (day.year, day.number);
+        Some(match answers.check(year, day, part, answer) {
+            Verdict::Unknown if record && answers.record(year, day, part, answer) => {
+                self.recorded += 1;
+                Checked::Recorded
+            }
+            Verdict::Unknown => {
+                self.unknown += 1;
+                Checked::Unknown
+            }
+            Verdict::Pass => {
+                self.passed += 1;
+                Checked::Pass
+            }
+            Verdict::Fail { expected } => {
+                self.failed = true;
+                self.wrong += 1;
+                Checked::Fail(expected)
+            }
+        })
+    }
+
+    /// Write the counts, if answers were checked at all
+    fn summary(&self, out: &mut impl Write) -> io::Result<()> {
+        if self.answers.is_none() {
+            return Ok(());
+        }
+        writeln!(
+            out,
+            "{} passed, {} failed, {} unknown, {} recorded",
+            self.passed, self.wrong, self.unknown, self.recorded
+        )
+    }
+}
+
+/// How long something took, and what it allocated if that was counted
+pub fn took(elapsed: Duration, usage: Option<Usage>) -> String {
+    match usage {
+        Some(usage) => format!("{:?}, {}", elapsed, usage),
+        None => format!("{:?}", elapsed),
+    }
+}
+
+/// Multi-line answers (e.g. day 13) read better starting on their own line
+pub fn on_own_line(answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("\n{}", answer)
+    } else {
+        answer.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runner;
+
+    use super::*;
+
+    #[test]
+    fn checks_and_records_answers() {
+        let day = runner::find_day(2021, 1).unwrap();
+        let mut answers = Answers::default();
+        answers.record(2021, 1, Part::Two, "wrong");
+
+        let mut out = vec![];
+        let mut tally = Tally::new(Some(&mut answers), true);
+        let (parts, source) = ([Part::One, Part::Two], InputSource::Embedded);
+        run(
+            &mut out,
+            &[day],
+            &parts,
+            &source,
+            Solving::default(),
+            &mut tally,
+        )
+        .unwrap();
+        assert!(tally.failed());
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(" [recorded]\n2021 day 1 part 2: "));
+        assert!(out.contains(" [FAIL, expected wrong]\n"));
+        assert!(out.ends_with("0 passed, 1 failed, 0 unknown, 1 recorded\n"));
+        assert!(answers.get(2021, 1, Part::One).is_some());
+    }
+
+    #[test]
+    fn lines_up_the_table() {
+        let day = runner::find_day(2021, 16).unwrap();
+        let rows = [
+            Row {
+                part: Some(Part::One),
+                answer: "7".to_string(),
+                solve: Some(Duration::from_millis(12)),
+                verdict: "pass".to_string(),
+                ..Row::failed(day, String::new(), Some(Duration::from_millis(1)))
+            },
+            Row::failed(day, "invalid input: oh no".to_string(), None),
+        ];
+        assert_eq!(
+            table(&rows),
+            "\
+year  day  part  answer                 parse    solve
+2021   16     1  7                     1.00ms  12.00ms  pass
+2021   16        invalid input: oh no
+"
+        );
+    }
+}
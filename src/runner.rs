@@ -0,0 +1,846 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::alloc::{self, Usage};
+use crate::input::InputSource;
+use crate::parse::ParseError;
+use crate::solution::Solution;
+use crate::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn from_number(n: u8) -> Option<Self> {
+        match n {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// A day's parsed input, whatever its type
+pub type Parsed = Box<dyn Any + Send + Sync>;
+
+/// Type-erased wrapper around a [`Solution`], so days with different input and answer types can
+/// live in the same registry
+pub trait Solver: Sync {
+    fn parse(&self, input: &str) -> Result<Parsed, ParseError>;
+
+    /// Panics if `parsed` didn't come from this solver's `parse`
+    fn solve(&self, parsed: &dyn Any, part: Part) -> String;
+}
+
+struct Erased<S>(PhantomData<fn() -> S>);
+
+impl<S> Solver for Erased<S>
+where
+    S: Solution,
+    S::Input: Send + Sync + 'static,
+{
+    fn parse(&self, input: &str) -> Result<Parsed, ParseError> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn solve(&self, parsed: &dyn Any, part: Part) -> String {
+        let input = parsed
+            .downcast_ref::<S::Input>()
+            .expect("parsed input from a different day");
+        match part {
+            Part::One => S::part1(input).to_string(),
+            Part::Two => S::part2(input).to_string(),
+        }
+    }
+}
+
+/// A single day in the registry
+pub struct Day {
+    pub year: u16,
+    pub number: u8,
+    pub solver: &'static dyn Solver,
+}
+
+macro_rules! day {
+    ($year:literal, $number:literal, $solution:ty) => {
+        Day {
+            year: $year,
+            number: $number,
+            solver: &Erased::<$solution>(PhantomData),
+        }
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    day!(2021, 1, y2021::day1::Day1),
+    day!(2021, 2, y2021::day2::Day2),
+    day!(2021, 3, y2021::day3::Day3),
+    day!(2021, 4, y2021::day4::Day4),
+    day!(2021, 5, y2021::day5::Day5),
+    day!(2021, 6, y2021::day6::Day6),
+    day!(2021, 7, y2021::day7::Day7),
+    day!(2021, 8, y2021::day8::Day8),
+    day!(2021, 9, y2021::day9::Day9),
+    day!(2021, 10, y2021::day10::Day10),
+    day!(2021, 11, y2021::day11::Day11),
+    day!(2021, 12, y2021::day12::Day12),
+    day!(2021, 13, y2021::day13::Day13),
+    day!(2021, 14, y2021::day14::Day14),
+    day!(2021, 15, y2021::day15::Day15),
+    day!(2021, 16, y2021::day16::Day16),
+];
+
+pub fn find_day(year: u16, number: u8) -> Option<&'static Day> {
+    DAYS.iter()
+        .find(|day| (day.year, day.number) == (year, number))
+}
+
+/// The days to run: `day` of `year` (or the latest year), or every day of `year` (or of every
+/// year) if there's no `day`
+pub fn select_days(year: Option<u16>, day: Option<u8>) -> Result<Vec<&'static Day>, String> {
+    match (year, day) {
+        (year, Some(number)) => {
+            let year = year.unwrap_or_else(latest_year);
+            match find_day(year, number) {
+                Some(day) => Ok(vec![day]),
+                None => Err(format!(
+                    "no solution registered for {} day {}",
+                    year, number
+                )),
+            }
+        }
+        (Some(year), None) => {
+            let days: Vec<_> = DAYS.iter().filter(|day| day.year == year).collect();
+            if days.is_empty() {
+                return Err(format!("no solutions registered for {}", year));
+            }
+            Ok(days)
+        }
+        (None, None) => Ok(DAYS.iter().collect()),
+    }
+}
+
+/// The most recent event with any days registered, which commands default to
+pub fn latest_year() -> u16 {
+    DAYS.iter()
+        .map(|day| day.year)
+        .max()
+        .expect("at least one day")
+}
+
+impl fmt::Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {}", self.year, self.number)
+    }
+}
+
+/// How long a part gets to run by default before it's given up on
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Why a part didn't produce an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// The solution panicked, with this message
+    Panic(String),
+    /// The solution was still running when it hit this time limit
+    Timeout(Duration),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Panic(message) => write!(f, "panicked: {}", message),
+            Failure::Timeout(limit) => write!(f, "timed out after {:?}", limit),
+        }
+    }
+}
+
+/// The result of solving a single part
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub part: Part,
+    /// The answer, or why there isn't one
+    pub result: Result<String, Failure>,
+    pub elapsed: Duration,
+    /// What solving allocated, if the run was profiled
+    pub usage: Option<Usage>,
+}
+
+/// Why a day's input couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseFailure {
+    /// The input was malformed
+    Invalid(ParseError),
+    /// The parser panicked, with this message
+    Panic(String),
+}
+
+impl fmt::Display for ParseFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseFailure::Invalid(e) => write!(f, "invalid input: {}", e),
+            ParseFailure::Panic(message) => write!(f, "parsing panicked: {}", message),
+        }
+    }
+}
+
+/// The result of running some or all parts of a day
+#[derive(Debug, Clone)]
+pub struct DayRun {
+    pub year: u16,
+    pub day: u8,
+    pub parse_time: Duration,
+    /// What parsing allocated, if the run was profiled
+    pub parse_usage: Option<Usage>,
+    /// The outcome of each part, or why parsing failed
+    pub result: Result<Vec<Outcome>, ParseFailure>,
+}
+
+/// Parse the input once, then solve each of the given parts in parallel, catching any panic. With
+/// a `limit`, a part that runs for longer is reported as timed out and cancelled; the parts are
+/// then waited for on the calling thread, which shouldn't be one of rayon's, since the parts may
+/// need them.
+pub fn run_day(day: &Day, input: &str, parts: &[Part], limit: Option<Duration>) -> DayRun {
+    run(day, input, parts, limit, false)
+}
+
+/// Like [`run_day`], but parse and solve each part one at a time, counting what each allocates.
+/// The counts are only there if [`alloc::Counting`] is the global allocator, and are only
+/// accurate if nothing else is running.
+pub fn profile_day(day: &Day, input: &str, parts: &[Part], limit: Option<Duration>) -> DayRun {
+    run(day, input, parts, limit, true)
+}
+
+fn run(day: &Day, input: &str, parts: &[Part], limit: Option<Duration>, profile: bool) -> DayRun {
+    let measure = |f: &mut dyn FnMut()| {
+        if profile {
+            alloc::measure(f).1
+        } else {
+            f();
+            None
+        }
+    };
+
+    let start = Instant::now();
+    let mut parsed = None;
+    let parse_usage = measure(&mut || parsed = Some(catch(|| day.solver.parse(input))));
+    let parsed = match parsed.expect("parsing ran") {
+        Ok(Ok(parsed)) => Ok(parsed),
+        Ok(Err(e)) => Err(ParseFailure::Invalid(e)),
+        Err(message) => Err(ParseFailure::Panic(message)),
+    };
+    let parse_time = start.elapsed();
+
+    let result = parsed.map(|parsed| {
+        let parsed = Arc::new(parsed);
+        let solve = |&part: &Part| {
+            let start = Instant::now();
+            let mut result = None;
+            let usage = measure(&mut || {
+                result = Some(match limit {
+                    None => catch(|| day.solver.solve(&**parsed, part)).map_err(Failure::Panic),
+                    Some(limit) => {
+                        Timed::start(day.solver, parsed.clone(), part, limit)
+                            .wait()
+                            .0
+                    }
+                })
+            });
+            Outcome {
+                part,
+                result: result.expect("solving ran"),
+                elapsed: start.elapsed(),
+                usage,
+            }
+        };
+        match limit {
+            _ if profile => parts.iter().map(solve).collect(),
+            None => parts.par_iter().map(solve).collect(),
+            // waiting on the rayon pool would keep its threads from the parts that use it, so
+            // every part starts at once and this thread waits for them
+            Some(limit) => {
+                let timed: Vec<_> = parts
+                    .iter()
+                    .map(|&part| (part, Timed::start(day.solver, parsed.clone(), part, limit)))
+                    .collect();
+                timed
+                    .into_iter()
+                    .map(|(part, timed)| {
+                        let (result, elapsed) = timed.wait();
+                        Outcome {
+                            part,
+                            result,
+                            elapsed,
+                            usage: None,
+                        }
+                    })
+                    .collect()
+            }
+        }
+    });
+
+    DayRun {
+        year: day.year,
+        day: day.number,
+        parse_time,
+        parse_usage,
+        result,
+    }
+}
+
+/// A part being solved on a thread of its own, which is given up on after a time limit. A
+/// thread can't be killed, so a part that times out is only told to stop: it keeps running in
+/// the background until it next calls [`check_cancelled`], or finishes.
+struct Timed {
+    receiver: mpsc::Receiver<(Result<String, String>, Duration)>,
+    cancelled: Arc<AtomicBool>,
+    start: Instant,
+    limit: Duration,
+}
+
+impl Timed {
+    fn start(
+        solver: &'static dyn Solver,
+        parsed: Arc<Parsed>,
+        part: Part,
+        limit: Duration,
+    ) -> Self {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = mpsc::channel();
+        let flag = cancelled.clone();
+        let start = Instant::now();
+        thread::spawn(move || {
+            CANCELLED.with(|cancelled| *cancelled.borrow_mut() = Some(flag));
+            let result = catch(|| solver.solve(&**parsed, part));
+            // nobody is listening any more if the part timed out
+            let _ = sender.send((result, start.elapsed()));
+        });
+        Self {
+            receiver,
+            cancelled,
+            start,
+            limit,
+        }
+    }
+
+    /// The answer and how long it took, or a timeout once the limit has passed since the start
+    fn wait(self) -> (Result<String, Failure>, Duration) {
+        let left = self.limit.saturating_sub(self.start.elapsed());
+        match self.receiver.recv_timeout(left) {
+            Ok((result, elapsed)) => (result.map_err(Failure::Panic), elapsed),
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                self.cancelled.store(true, Ordering::Relaxed);
+                (Err(Failure::Timeout(self.limit)), self.start.elapsed())
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                unreachable!("the part always sends a result")
+            }
+        }
+    }
+}
+
+thread_local! {
+    /// Set on the thread solving a part with a time limit, for when it runs out of time
+    static CANCELLED: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+}
+
+/// The payload of the unwind [`check_cancelled`] starts
+struct Cancelled;
+
+/// Whether the part being solved on this thread has run out of time and should stop
+pub fn cancelled() -> bool {
+    CANCELLED.with(|cancelled| {
+        cancelled
+            .borrow()
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed))
+    })
+}
+
+/// Stop solving if the part has run out of time. Solutions that might loop for a long time call
+/// this now and then, so a timeout doesn't leave them running in the background.
+pub fn check_cancelled() {
+    if cancelled() {
+        // not a panic, so the panic hook doesn't report it
+        panic::resume_unwind(Box::new(Cancelled));
+    }
+}
+
+impl DayRun {
+    /// A record of each part, or a single record of why the input couldn't be parsed
+    pub fn records(&self) -> Vec<Record> {
+        let parse_ns = Some(self.parse_time.as_nanos() as u64);
+        let outcomes = match &self.result {
+            Ok(outcomes) => outcomes,
+            Err(failure) => {
+                let error = failure.to_string();
+                return vec![Record {
+                    parse_ns,
+                    parse_memory: self.parse_usage,
+                    ..Record::error(self.year, self.day, None, Variant::Slow, error)
+                }];
+            }
+        };
+
+        outcomes
+            .iter()
+            .map(|outcome| {
+                let (answer, error) = match &outcome.result {
+                    Ok(answer) => (Some(answer.clone()), None),
+                    Err(failure) => (None, Some(failure.to_string())),
+                };
+                Record {
+                    year: self.year,
+                    day: self.day,
+                    part: Some(outcome.part.number()),
+                    variant: Variant::Slow,
+                    answer,
+                    parse_ns,
+                    solve_ns: Some(outcome.elapsed.as_nanos() as u64),
+                    verdict: None,
+                    expected: None,
+                    error,
+                    parse_memory: self.parse_usage,
+                    solve_memory: outcome.usage,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Which implementation of a part produced an answer: the original solution, or its `fast`
+/// rewrite
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Variant {
+    Slow,
+    Fast,
+}
+
+/// One line of machine-readable output: how a single part went, or why a day couldn't be run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    /// Missing if the whole day failed, e.g. because its input couldn't be parsed
+    pub part: Option<u8>,
+    pub variant: Variant,
+    pub answer: Option<String>,
+    pub parse_ns: Option<u64>,
+    /// For a `fast` variant, which parses and solves in one go, this includes parsing
+    pub solve_ns: Option<u64>,
+    /// `pass`, `fail`, `unknown` or `recorded`, if the answer was checked
+    pub verdict: Option<String>,
+    /// The right answer, if this one was wrong
+    pub expected: Option<String>,
+    pub error: Option<String>,
+    /// What parsing allocated, only there if the run was profiled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_memory: Option<Usage>,
+    /// What solving allocated, only there if the run was profiled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solve_memory: Option<Usage>,
+}
+
+impl Record {
+    /// A record of something going wrong before there was an answer
+    pub fn error(year: u16, day: u8, part: Option<Part>, variant: Variant, error: String) -> Self {
+        Self {
+            year,
+            day,
+            part: part.map(Part::number),
+            variant,
+            answer: None,
+            parse_ns: None,
+            solve_ns: None,
+            verdict: None,
+            expected: None,
+            error: Some(error),
+            parse_memory: None,
+            solve_memory: None,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("records are always valid JSON")
+    }
+}
+
+/// How to run each day: with a time limit on each part, and whether to count allocations
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Solving {
+    pub limit: Option<Duration>,
+    pub profile: bool,
+}
+
+impl Solving {
+    pub fn run_day(self, day: &Day, input: &str, parts: &[Part]) -> DayRun {
+        if self.profile {
+            profile_day(day, input, parts, self.limit)
+        } else {
+            run_day(day, input, parts, self.limit)
+        }
+    }
+
+    /// Every day at once, or one at a time if allocations are being counted
+    pub fn run_days(self, days: &[(&Day, &str)], parts: &[Part]) -> Vec<DayRun> {
+        if self.profile {
+            days.iter()
+                .map(|(day, input)| self.run_day(day, input, parts))
+                .collect()
+        } else {
+            run_days(days, parts, self.limit)
+        }
+    }
+}
+
+/// Run a single part on the day's input from `source`, for its answer
+pub fn solve(year: u16, day: u8, part: Part, source: &InputSource) -> Result<String, String> {
+    let solver = find_day(year, day).ok_or("no solution registered")?;
+    let input = source.load(year, day).map_err(|e| e.to_string())?;
+    let run = run_day(solver, &input, &[part], Some(DEFAULT_TIMEOUT));
+    let mut outcomes = run.result.map_err(|failure| failure.to_string())?;
+    outcomes
+        .pop()
+        .expect("an outcome for the part")
+        .result
+        .map_err(|failure| failure.to_string())
+}
+
+/// Run each day on its own input, all at once, in the same order as `days`. A panic or timeout
+/// only affects the day (or part) it happened in. Without a limit the days run on the rayon
+/// pool; with one, each day waits for its parts on a thread of its own instead, leaving the pool
+/// to the parts.
+pub fn run_days(days: &[(&Day, &str)], parts: &[Part], limit: Option<Duration>) -> Vec<DayRun> {
+    if limit.is_none() {
+        return days
+            .par_iter()
+            .map(|(day, input)| run_day(day, input, parts, limit))
+            .collect();
+    }
+    thread::scope(|scope| {
+        let running: Vec<_> = days
+            .iter()
+            .map(|(day, input)| scope.spawn(move || run_day(day, input, parts, limit)))
+            .collect();
+        running
+            .into_iter()
+            .map(|day| day.join().expect("panics are caught"))
+            .collect()
+    })
+}
+
+/// Run `f`, turning a panic into its message
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(panic_message)
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else if payload.is::<Cancelled>() {
+        "cancelled".to_string()
+    } else {
+        "<non-string panic payload>".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::Span;
+
+    use super::*;
+
+    #[test]
+    fn registry_is_sorted_and_unique() {
+        let numbers: Vec<_> = DAYS.iter().map(|day| (day.year, day.number)).collect();
+        let mut sorted = numbers.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(numbers, sorted);
+    }
+
+    #[test]
+    fn find_day_by_number() {
+        let day = find_day(2021, 9).unwrap();
+        assert_eq!((day.year, day.number), (2021, 9));
+        assert_eq!(day.to_string(), "2021 day 9");
+        assert!(find_day(2021, 0).is_none());
+        assert!(find_day(2021, 26).is_none());
+        assert!(find_day(2015, 9).is_none());
+        assert_eq!(latest_year(), 2021);
+    }
+
+    #[test]
+    fn selects_days() {
+        let days = select_days(None, Some(9)).unwrap();
+        assert_eq!(days[0].to_string(), "2021 day 9");
+        let in_2021 = DAYS.iter().filter(|day| day.year == 2021).count();
+        assert_eq!(select_days(Some(2021), None).unwrap().len(), in_2021);
+        assert_eq!(select_days(None, None).unwrap().len(), DAYS.len());
+        assert!(select_days(Some(2015), Some(9)).is_err());
+        assert!(select_days(Some(2015), None).is_err());
+    }
+
+    struct Numbers;
+
+    impl Solution for Numbers {
+        type Input = Vec<usize>;
+        type Answer1 = usize;
+        type Answer2 = usize;
+
+        fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+            if input == "panic" {
+                panic!("parser panicked");
+            }
+            Span::lines(input)
+                .map(|line| line.parse("a number"))
+                .collect()
+        }
+
+        fn part1(input: &Vec<usize>) -> usize {
+            input.iter().sum()
+        }
+
+        fn part2(_: &Vec<usize>) -> usize {
+            panic!("{} {}", "formatted", 2)
+        }
+    }
+
+    #[test]
+    fn runs_requested_parts() {
+        let day = day!(2000, 99, Numbers);
+        let run = run_day(&day, "1\n2\n3", &[Part::One], None);
+        assert_eq!(run.day, 99);
+        let outcomes = run.result.unwrap();
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].part, Part::One);
+        assert_eq!(outcomes[0].result, Ok("6".to_string()));
+    }
+
+    #[test]
+    fn catches_panics() {
+        let day = day!(2000, 99, Numbers);
+        let outcomes = run_day(&day, "1", &[Part::One, Part::Two], None)
+            .result
+            .unwrap();
+        assert_eq!(outcomes[0].result, Ok("1".to_string()));
+        assert_eq!(
+            outcomes[1].result,
+            Err(Failure::Panic("formatted 2".to_string()))
+        );
+
+        let run = run_day(&day, "panic", &[Part::One, Part::Two], None);
+        assert_eq!(
+            run.result.unwrap_err(),
+            ParseFailure::Panic("parser panicked".to_string())
+        );
+    }
+
+    #[test]
+    fn runs_days_together() {
+        let (numbers, other) = (day!(2000, 98, Numbers), day!(2000, 99, Numbers));
+        let runs = run_days(
+            &[(&numbers, "1\n2"), (&other, "panic"), (&numbers, "5")],
+            &[Part::One, Part::Two],
+            None,
+        );
+
+        assert_eq!(
+            runs.iter().map(|run| run.day).collect::<Vec<_>>(),
+            [98, 99, 98]
+        );
+        let first = runs[0].result.as_ref().unwrap();
+        assert_eq!(first[0].result, Ok("3".to_string()));
+        assert_eq!(
+            first[1].result,
+            Err(Failure::Panic("formatted 2".to_string()))
+        );
+        assert!(runs[1].result.is_err());
+        assert_eq!(
+            runs[2].result.as_ref().unwrap()[0].result,
+            Ok("5".to_string())
+        );
+    }
+
+    #[test]
+    fn records_each_part() {
+        let day = day!(2000, 99, Numbers);
+        let records = run_day(&day, "1\n2", &[Part::One, Part::Two], None).records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            (records[0].part, records[0].answer.as_deref()),
+            (Some(1), Some("3"))
+        );
+        assert_eq!(records[1].error.as_deref(), Some("panicked: formatted 2"));
+        assert!(records[0].solve_ns.is_some());
+        assert!(records[0].to_json().starts_with(
+            r#"{"year":2000,"day":99,"part":1,"variant":"slow","answer":"3","parse_ns":"#
+        ));
+
+        let records = run_day(&day, "x", &[Part::One], None).records();
+        assert_eq!(
+            records[0].to_json(),
+            format!(
+                r#"{{"year":2000,"day":99,"part":null,"variant":"slow","answer":null,"parse_ns":{},"solve_ns":null,"verdict":null,"expected":null,"error":"invalid input: line 1, column 1: expected a number, found \"x\""}}"#,
+                records[0].parse_ns.unwrap()
+            )
+        );
+    }
+
+    static STOPPED: AtomicBool = AtomicBool::new(false);
+
+    struct Forever;
+
+    impl Solution for Forever {
+        type Input = ();
+        type Answer1 = usize;
+        type Answer2 = usize;
+
+        fn parse(_: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn part1(_: &()) -> usize {
+            struct Stopped;
+            impl Drop for Stopped {
+                fn drop(&mut self) {
+                    STOPPED.store(true, Ordering::SeqCst);
+                }
+            }
+
+            let _stopped = Stopped;
+            loop {
+                check_cancelled();
+                thread::yield_now();
+            }
+        }
+
+        fn part2(_: &()) -> usize {
+            2
+        }
+    }
+
+    #[test]
+    fn times_out_and_cancels() {
+        let day = day!(2000, 99, Forever);
+        let limit = Duration::from_millis(50);
+        let outcomes = run_day(&day, "", &[Part::One, Part::Two], Some(limit))
+            .result
+            .unwrap();
+        assert_eq!(outcomes[0].result, Err(Failure::Timeout(limit)));
+        assert_eq!(outcomes[1].result, Ok("2".to_string()));
+        assert_eq!(Failure::Timeout(limit).to_string(), "timed out after 50ms");
+
+        let start = Instant::now();
+        while !STOPPED.load(Ordering::SeqCst) {
+            assert!(start.elapsed() < Duration::from_secs(10), "never cancelled");
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert!(!cancelled());
+
+        // the limit doesn't change how other days go
+        let numbers = day!(2000, 98, Numbers);
+        let runs = run_days(
+            &[(&day, ""), (&numbers, "1\n2")],
+            &[Part::One, Part::Two],
+            Some(limit),
+        );
+        assert!(runs[0].result.as_ref().unwrap()[0].result.is_err());
+        let numbers = runs[1].result.as_ref().unwrap();
+        assert_eq!(numbers[0].result, Ok("3".to_string()));
+        assert_eq!(
+            numbers[1].result,
+            Err(Failure::Panic("formatted 2".to_string()))
+        );
+    }
+
+    struct Parallel;
+
+    impl Solution for Parallel {
+        type Input = ();
+        type Answer1 = usize;
+        type Answer2 = usize;
+
+        fn parse(_: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn part1(_: &()) -> usize {
+            (1..=100usize).into_par_iter().sum()
+        }
+
+        fn part2(_: &()) -> usize {
+            (1..=100usize).into_par_iter().map(|n| n % 2).sum()
+        }
+    }
+
+    #[test]
+    fn timed_parts_can_use_rayon() {
+        // more days than the pool has threads, so if the days waited for their parts on the
+        // pool, the parts could never use it
+        let day = day!(2000, 97, Parallel);
+        let days = vec![(&day, ""); rayon::current_num_threads() + 1];
+        let limit = Duration::from_secs(30);
+        let start = Instant::now();
+        for run in run_days(&days, &[Part::One, Part::Two], Some(limit)) {
+            let outcomes = run.result.unwrap();
+            assert_eq!(outcomes[0].result, Ok("5050".to_string()));
+            assert_eq!(outcomes[1].result, Ok("50".to_string()));
+        }
+        let outcomes = run_day(&day, "", &[Part::One, Part::Two], Some(limit))
+            .result
+            .unwrap();
+        assert_eq!(outcomes[1].result, Ok("50".to_string()));
+        assert!(start.elapsed() < limit);
+    }
+
+    #[test]
+    fn profiles_allocations() {
+        let day = day!(2000, 99, Numbers);
+        let run = profile_day(&day, "1\n2\n3", &[Part::One, Part::Two], None);
+        // parsing collects into a `Vec`, so allocates at least once
+        assert!(run.parse_usage.unwrap().allocations >= 1);
+        let outcomes = run.result.as_ref().unwrap();
+        assert_eq!(outcomes[0].result, Ok("6".to_string()));
+        assert!(outcomes.iter().all(|outcome| outcome.usage.is_some()));
+        let json = run.records()[0].to_json();
+        assert!(json.contains(r#""parse_memory":{"allocations":"#));
+        assert!(json.contains(r#""solve_memory":{"allocations":"#));
+
+        let run = run_day(&day, "1\n2\n3", &[Part::One], None);
+        assert!(run.parse_usage.is_none() && run.result.unwrap()[0].usage.is_none());
+    }
+
+    #[test]
+    fn reports_invalid_input() {
+        let day = day!(2000, 99, Numbers);
+        let run = run_day(&day, "1\n2\noh no", &[Part::One], None);
+        assert_eq!(
+            run.result.unwrap_err(),
+            ParseFailure::Invalid(ParseError::new(3, 1, "a number", "\"oh no\""))
+        );
+    }
+}
@@ -0,0 +1,355 @@
+//! Generating the boilerplate for a new day: its module, an empty input file, and its entries
+//! in its year's module and [`crate::runner::DAYS`]. The first day of a new year also gets the
+//! year's module, declared in `lib.rs`.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::input;
+
+/// The module for a new day, with `YYYY` and `NN` standing for the year and day
+const TEMPLATE: &str = r##"use crate::parse::{ParseError, Span};
+use crate::solution::Solution;
+
+pub struct DayNN;
+
+impl Solution for DayNN {
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<String>) -> usize {
+        solution1(input)
+    }
+
+    fn part2(input: &Vec<String>) -> usize {
+        solution2(input)
+    }
+}
+
+pub fn parse_input(s: &str) -> Result<Vec<String>, ParseError> {
+    Ok(Span::lines(s).map(|line| line.as_str().to_string()).collect())
+}
+
+pub fn solution1(input: &[String]) -> usize {
+    todo!("YYYY day NN part 1, from {} lines", input.len())
+}
+
+pub fn solution2(input: &[String]) -> usize {
+    todo!("YYYY day NN part 2, from {} lines", input.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input_const;
+
+    use super::*;
+
+    const GIVEN_INPUT: &str = r#""#;
+
+    #[test]
+    #[ignore = "needs the example from the puzzle"]
+    fn given_example() {
+        let input = parse_input(GIVEN_INPUT).unwrap();
+        assert_eq!(solution1(&input), 0);
+        assert_eq!(solution2(&input), 0);
+    }
+
+    #[test]
+    #[ignore = "needs the puzzle input in src/inputs/YYYY/inputNN"]
+    fn parse_real_input() {
+        let input = parse_input(input_const!(YYYY, NN)).unwrap();
+        assert!(!input.is_empty());
+    }
+}
+"##;
+
+/// The source of a new `dayN.rs`
+pub fn day_module(year: u16, day: u8) -> String {
+    TEMPLATE
+        .replace("YYYY", &year.to_string())
+        .replace("NN", &day.to_string())
+}
+
+/// The source of a new year's module, before any days are registered in it
+pub fn year_module(year: u16) -> String {
+    format!("//! Advent of Code {}\n\n", year)
+}
+
+/// `lib_rs` with a `pub mod` for `year`, among the other years in order
+pub fn register_year(lib_rs: &str, year: u16) -> Result<String, String> {
+    let new = format!("pub mod y{};", year);
+    insert_in_order(lib_rs, year, &new, |line| {
+        line.strip_prefix("pub mod y")?
+            .strip_suffix(';')?
+            .parse()
+            .ok()
+    })
+    .ok_or_else(|| "no `pub mod yYYYY;` lines".to_string())?
+    .map_err(|()| format!("{} is already declared", year))
+}
+
+/// `mod_rs`, a year's module, with a `pub mod` for `day` among the other days in order
+pub fn register_module(mod_rs: &str, day: u8) -> Result<String, String> {
+    let new = format!("pub mod day{};", day);
+    let with_day = match insert_in_order(mod_rs, day, &new, |line| {
+        line.strip_prefix("pub mod day")?
+            .strip_suffix(';')?
+            .parse()
+            .ok()
+    }) {
+        Some(result) => result.map_err(|()| format!("day {} is already declared", day))?,
+        // the year's first day
+        None => format!("{}{}\n", mod_rs, new),
+    };
+    Ok(with_day)
+}
+
+/// `runner_rs` with the day added to [`crate::runner::DAYS`], among the other days in order
+pub fn register_day(runner_rs: &str, year: u16, day: u8) -> Result<String, String> {
+    let entry = format!(
+        "    day!({}, {}, y{}::day{}::Day{}),",
+        year, day, year, day, day
+    );
+    insert_in_order(runner_rs, (year, day), &entry, |line| {
+        let mut args = line.trim().strip_prefix("day!(")?.split(", ");
+        Some((args.next()?.parse().ok()?, args.next()?.parse().ok()?))
+    })
+    .ok_or_else(|| "no `day!(YYYY, N, ...)` entries".to_string())?
+    .map_err(|()| format!("{} day {} is already registered", year, day))
+}
+
+/// Insert `new` after the last line that `key` gives a smaller key than `at` for, failing if a
+/// line already has `at`. `None` if no lines have a key at all.
+fn insert_in_order<K: Ord>(
+    s: &str,
+    at: K,
+    new: &str,
+    key: impl Fn(&str) -> Option<K>,
+) -> Option<Result<String, ()>> {
+    let mut lines: Vec<&str> = s.lines().collect();
+    let keyed: Vec<(usize, K)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, key(line)?)))
+        .collect();
+    if keyed.is_empty() {
+        return None;
+    }
+    if keyed.iter().any(|(_, k)| *k == at) {
+        return Some(Err(()));
+    }
+
+    let at = match keyed.iter().rev().find(|(_, k)| *k < at) {
+        Some(&(i, _)) => i + 1,
+        None => keyed[0].0,
+    };
+    lines.insert(at, new);
+    let mut result = lines.join("\n");
+    if s.ends_with('\n') {
+        result.push('\n');
+    }
+    Some(Ok(result))
+}
+
+/// Everything `scaffold` created or changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scaffolded {
+    pub module: PathBuf,
+    pub input: PathBuf,
+    pub registered: Vec<PathBuf>,
+}
+
+/// Create the module and empty input file for `year`'s `day` in the crate at `root`, and
+/// register it, adding the year's module if it's the first day of the year. Nothing is written
+/// unless every step can be done.
+pub fn scaffold(root: &Path, year: u16, day: u8) -> Result<Scaffolded, ScaffoldError> {
+    let src = root.join("src");
+    let year_dir = src.join(format!("y{}", year));
+    let module = year_dir.join(format!("day{}.rs", day));
+    let module_dir = year_dir.join(format!("day{}", day));
+    let input = input::path_in(&src.join("inputs"), year, day);
+    for path in [&module, &module_dir, &input] {
+        if path.exists() {
+            return Err(ScaffoldError::Exists(path.clone()));
+        }
+    }
+
+    // every file to change, with its new contents
+    let mut registered = vec![];
+    let year_path = year_dir.join("mod.rs");
+    let year_rs = if year_path.exists() {
+        register(&year_path, |mod_rs| register_module(mod_rs, day))?
+    } else {
+        let lib_path = src.join("lib.rs");
+        let lib_rs = register(&lib_path, |lib_rs| register_year(lib_rs, year))?;
+        registered.push((lib_path, lib_rs));
+        register_module(&year_module(year), day).expect("a new year has no days")
+    };
+    registered.push((year_path, year_rs));
+    let runner_path = src.join("runner.rs");
+    let runner_rs = register(&runner_path, |runner_rs| register_day(runner_rs, year, day))?;
+    registered.push((runner_path, runner_rs));
+
+    let input_dir = input.parent().expect("inputs are in a year's directory");
+    for dir in [year_dir.as_path(), input_dir] {
+        fs::create_dir_all(dir).map_err(|source| ScaffoldError::Io {
+            path: dir.to_path_buf(),
+            source,
+        })?;
+    }
+    write(&module, &day_module(year, day))?;
+    write(&input, "")?;
+    for (path, contents) in &registered {
+        write(path, contents)?;
+    }
+    Ok(Scaffolded {
+        module,
+        input,
+        registered: registered.into_iter().map(|(path, _)| path).collect(),
+    })
+}
+
+/// The contents of `path` after `add` registers something in it
+fn register(
+    path: &Path,
+    add: impl FnOnce(&str) -> Result<String, String>,
+) -> Result<String, ScaffoldError> {
+    add(&read(path)?).map_err(|message| ScaffoldError::Register(path.to_path_buf(), message))
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|source| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    fs::write(path, contents).map_err(|source| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// The day already has a module or input
+    Exists(PathBuf),
+    /// The file to register the day in didn't look as expected
+    Register(PathBuf, String),
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::Register(path, message) => {
+                write!(f, "couldn't register in {}: {}", path.display(), message)
+            }
+            ScaffoldError::Io { path, source } => {
+                write!(f, "couldn't access {}: {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_in_order() {
+        let mod_rs = "//! Advent of Code 2021\n\npub mod day1;\npub mod day2;\npub mod day10;\n";
+        assert_eq!(
+            register_module(mod_rs, 3).unwrap(),
+            "//! Advent of Code 2021\n\npub mod day1;\npub mod day2;\npub mod day3;\npub mod day10;\n"
+        );
+        assert_eq!(
+            register_module(mod_rs, 11).unwrap(),
+            "//! Advent of Code 2021\n\npub mod day1;\npub mod day2;\npub mod day10;\npub mod day11;\n"
+        );
+        assert!(register_module(mod_rs, 2).is_err());
+        assert_eq!(
+            register_module(&year_module(2022), 4).unwrap(),
+            "//! Advent of Code 2022\n\npub mod day4;\n"
+        );
+
+        let lib_rs = "pub mod grid;\nmod utils;\npub mod y2021;\n";
+        assert_eq!(
+            register_year(lib_rs, 2022).unwrap(),
+            "pub mod grid;\nmod utils;\npub mod y2021;\npub mod y2022;\n"
+        );
+        assert!(register_year(lib_rs, 2021).is_err());
+        assert!(register_year("pub mod grid;", 2021).is_err());
+
+        let runner_rs = "pub const DAYS: &[Day] = &[\n    day!(2021, 1, y2021::day1::Day1),\n    day!(2021, 16, y2021::day16::Day16),\n];";
+        assert_eq!(
+            register_day(runner_rs, 2021, 17).unwrap(),
+            "pub const DAYS: &[Day] = &[\n    day!(2021, 1, y2021::day1::Day1),\n    day!(2021, 16, y2021::day16::Day16),\n    day!(2021, 17, y2021::day17::Day17),\n];"
+        );
+        assert_eq!(
+            register_day(runner_rs, 2020, 25).unwrap(),
+            "pub const DAYS: &[Day] = &[\n    day!(2020, 25, y2020::day25::Day25),\n    day!(2021, 1, y2021::day1::Day1),\n    day!(2021, 16, y2021::day16::Day16),\n];"
+        );
+        assert!(register_day(runner_rs, 2021, 16).is_err());
+    }
+
+    #[test]
+    fn generates_a_day() {
+        let root = std::env::temp_dir().join(format!("advent-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/y2021")).unwrap();
+        fs::write(root.join("src/lib.rs"), "pub mod runner;\npub mod y2021;\n").unwrap();
+        fs::write(root.join("src/y2021/mod.rs"), "pub mod day1;\n").unwrap();
+        fs::write(
+            root.join("src/runner.rs"),
+            "const DAYS: &[Day] = &[\n    day!(2021, 1, y2021::day1::Day1),\n];\n",
+        )
+        .unwrap();
+
+        let scaffolded = scaffold(&root, 2021, 17).unwrap();
+        let module = fs::read_to_string(&scaffolded.module).unwrap();
+        assert!(module.contains("impl Solution for Day17 {"));
+        assert!(module.contains("input_const!(2021, 17)"));
+        assert!(!module.contains("NN") && !module.contains("YYYY"));
+        assert_eq!(fs::read_to_string(&scaffolded.input).unwrap(), "");
+        assert_eq!(scaffolded.registered.len(), 2);
+        assert_eq!(
+            fs::read_to_string(root.join("src/y2021/mod.rs")).unwrap(),
+            "pub mod day1;\npub mod day17;\n"
+        );
+        assert!(fs::read_to_string(root.join("src/runner.rs"))
+            .unwrap()
+            .contains("    day!(2021, 17, y2021::day17::Day17),\n"));
+        assert!(matches!(
+            scaffold(&root, 2021, 17),
+            Err(ScaffoldError::Exists(_))
+        ));
+
+        let scaffolded = scaffold(&root, 2022, 1).unwrap();
+        assert_eq!(
+            scaffolded.module,
+            root.join("src").join("y2022").join("day1.rs")
+        );
+        assert_eq!(scaffolded.registered.len(), 3);
+        assert_eq!(
+            fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+            "pub mod runner;\npub mod y2021;\npub mod y2022;\n"
+        );
+        assert!(root.join("src/inputs/2022/input1").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,17 @@
+use std::fmt::Display;
+
+use crate::parse::ParseError;
+
+/// A day's puzzle: parse the input once, then solve each part from the parsed input
+pub trait Solution {
+    /// The parsed puzzle input, shared between both parts
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Self::Answer1;
+
+    fn part2(input: &Self::Input) -> Self::Answer2;
+}
@@ -0,0 +1,668 @@
+//! Submitting answers to the Advent of Code site, keeping a history of every guess so that a
+//! known-wrong answer is never sent twice.
+//!
+//! The site says whether a wrong answer was too high or too low, so the history also gives
+//! bounds that a numeric answer has to be between to stand a chance.
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::fetch::{Http, DEFAULT_ENDPOINT, USER_AGENT};
+use crate::runner::Part;
+
+/// What the site made of an answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without saying which way
+    Wrong,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+        }
+    }
+}
+
+/// The site's response to a submission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    Verdict(Verdict),
+    /// An answer was submitted too recently, and this is how long is left to wait, if the site
+    /// said
+    Wait(Option<Duration>),
+    /// The part has already been solved, or part 2 isn't unlocked yet
+    WrongLevel,
+    /// Anything else, with the text of the response
+    Unrecognized(String),
+}
+
+/// Work out what a response page says, from the text of its `<article>`
+pub fn parse_response(html: &str) -> Response {
+    let text = article_text(html);
+    if text.contains("That's the right answer") {
+        Response::Verdict(Verdict::Correct)
+    } else if text.contains("That's not the right answer") {
+        Response::Verdict(if text.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        })
+    } else if text.contains("You gave an answer too recently") {
+        Response::Wait(parse_wait(&text))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Response::WrongLevel
+    } else {
+        Response::Unrecognized(text)
+    }
+}
+
+/// The text inside the page's `<article>` (or the whole page if there isn't one), without tags
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .map(|(_, rest)| {
+            rest.split_once("</article>")
+                .map_or(rest, |(inner, _)| inner)
+        })
+        .unwrap_or(html);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// e.g. `You have 1m 30s left to wait`
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    wait.split_whitespace()
+        .map(|part| {
+            let (number, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let number: u64 = number.parse().ok()?;
+            match unit {
+                "h" => Some(number * 3600),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// One answer that was submitted, and what the site said about it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guess {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Whether an answer is worth submitting, going by the earlier guesses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    /// Nothing known rules it out
+    Plausible,
+    /// This exact answer was already submitted
+    AlreadyGuessed(Verdict),
+    /// The part has already been solved, with this answer
+    AlreadySolved(String),
+    /// At least as high as an answer that was too high
+    AboveBound(String),
+    /// At least as low as an answer that was too low
+    BelowBound(String),
+}
+
+/// Every guess made for one day, kept as a JSON object per line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History {
+    year: u16,
+    day: u8,
+    path: PathBuf,
+    guesses: Vec<Guess>,
+}
+
+impl History {
+    /// The history for `day`, kept alongside its input in a directory laid out like
+    /// `src/inputs`. It's empty if nothing has been guessed yet.
+    pub fn load(dir: &Path, year: u16, day: u8) -> Result<Self, SubmitError> {
+        let path = dir
+            .join(year.to_string())
+            .join(format!("guesses{}.jsonl", day));
+        let s = match fs::read_to_string(&path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(source) => return Err(SubmitError::Io { path, source }),
+        };
+        let guesses = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(i, line)| {
+                serde_json::from_str(line).map_err(|e| SubmitError::History {
+                    path: path.clone(),
+                    line: i + 1,
+                    message: e.to_string(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            year,
+            day,
+            path,
+            guesses,
+        })
+    }
+
+    pub fn guesses(&self) -> &[Guess] {
+        &self.guesses
+    }
+
+    /// Whether `answer` is worth submitting for `part`
+    pub fn check(&self, part: Part, answer: &str) -> Check {
+        let guesses = || self.guesses.iter().filter(|g| g.part == part.number());
+        if let Some(correct) = guesses().find(|g| g.verdict == Verdict::Correct) {
+            return Check::AlreadySolved(correct.answer.clone());
+        }
+        if let Some(guess) = guesses().find(|g| g.answer == answer) {
+            return Check::AlreadyGuessed(guess.verdict);
+        }
+
+        let Ok(n) = answer.parse::<i128>() else {
+            return Check::Plausible;
+        };
+        let bound = |verdict| {
+            guesses()
+                .filter(move |g| g.verdict == verdict)
+                .filter_map(|g| Some((g.answer.parse::<i128>().ok()?, &g.answer)))
+        };
+        if let Some((_, high)) = bound(Verdict::TooHigh).filter(|(h, _)| n >= *h).min() {
+            return Check::AboveBound(high.clone());
+        }
+        if let Some((_, low)) = bound(Verdict::TooLow).filter(|(l, _)| n <= *l).max() {
+            return Check::BelowBound(low.clone());
+        }
+        Check::Plausible
+    }
+
+    /// Add a guess, saving it straight away
+    pub fn record(&mut self, guess: Guess) -> Result<(), SubmitError> {
+        let line = serde_json::to_string(&guess).expect("guesses are always valid JSON");
+        let dir = self
+            .path
+            .parent()
+            .expect("guesses are in a year's directory");
+        fs::create_dir_all(dir)
+            .and_then(|()| {
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)
+            })
+            .and_then(|mut file| writeln!(file, "{}", line))
+            .map_err(|source| SubmitError::Io {
+                path: self.path.clone(),
+                source,
+            })?;
+        self.guesses.push(guess);
+        Ok(())
+    }
+}
+
+/// Posts answers to the site
+pub struct Submitter<H> {
+    http: H,
+    endpoint: String,
+    session: String,
+}
+
+impl<H: Http> Submitter<H> {
+    pub fn new(http: H, session: String) -> Self {
+        Self {
+            http,
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            session,
+        }
+    }
+
+    /// Submit somewhere other than the real site, e.g. `http://localhost:8080`
+    pub fn endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+    ) -> Result<Response, SubmitError> {
+        let url = format!("{}/{}/day/{}/answer", self.endpoint, year, day);
+        let cookie = format!("session={}", self.session);
+        let level = part.number().to_string();
+        let (status, body) = self
+            .http
+            .post_form(
+                &url,
+                &[("Cookie", &cookie), ("User-Agent", USER_AGENT)],
+                &[("level", &level), ("answer", answer)],
+            )
+            .map_err(SubmitError::Http)?;
+        match status {
+            200 => Ok(parse_response(&body)),
+            _ => Err(SubmitError::Status { status, body }),
+        }
+    }
+
+    /// Submit `answer` for the day `history` is for, unless the earlier guesses show it's wrong,
+    /// or show it's probably wrong and it isn't `force`d. Writes what's happening, and records
+    /// the guess. Returns whether the answer is correct.
+    pub fn submit_checked(
+        &self,
+        out: &mut impl Write,
+        history: &mut History,
+        part: Part,
+        answer: &str,
+        force: bool,
+    ) -> Result<bool, SubmitError> {
+        let bound = match history.check(part, answer) {
+            Check::Plausible => None,
+            Check::AboveBound(high) => Some((high, Verdict::TooHigh)),
+            Check::BelowBound(low) => Some((low, Verdict::TooLow)),
+            Check::AlreadySolved(correct) => {
+                writeln!(out, "already solved with {}", correct).map_err(SubmitError::Output)?;
+                return Ok(correct == answer);
+            }
+            Check::AlreadyGuessed(verdict) => {
+                return Err(SubmitError::AlreadyGuessed {
+                    answer: answer.to_string(),
+                    verdict,
+                });
+            }
+        };
+        if let Some((bound, verdict)) = bound {
+            if !force {
+                return Err(SubmitError::Implausible {
+                    answer: answer.to_string(),
+                    bound,
+                    verdict,
+                });
+            }
+            eprintln!(
+                "warning: {} was already {}, so {} probably is too",
+                bound, verdict, answer
+            );
+        }
+
+        let (year, day) = (history.year, history.day);
+        writeln!(
+            out,
+            "submitting {} for {} day {} part {}",
+            answer,
+            year,
+            day,
+            part.number()
+        )
+        .map_err(SubmitError::Output)?;
+        let verdict = match self.submit(year, day, part, answer)? {
+            Response::Verdict(verdict) => verdict,
+            response => return Err(SubmitError::Rejected(response)),
+        };
+
+        writeln!(out, "{}", verdict).map_err(SubmitError::Output)?;
+        history.record(Guess {
+            part: part.number(),
+            answer: answer.to_string(),
+            verdict,
+        })?;
+        Ok(verdict == Verdict::Correct)
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    /// An unexpected response
+    Status {
+        status: u16,
+        body: String,
+    },
+    /// No response at all
+    Http(String),
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    History {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+    /// This exact answer was submitted before
+    AlreadyGuessed {
+        answer: String,
+        verdict: Verdict,
+    },
+    /// An earlier guess shows the answer is probably wrong too
+    Implausible {
+        answer: String,
+        bound: String,
+        verdict: Verdict,
+    },
+    /// The site didn't give a verdict
+    Rejected(Response),
+    /// Couldn't write what was happening, e.g. because stdout was closed
+    Output(io::Error),
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Status { status, body } => {
+                let first_line = body.lines().next().unwrap_or("");
+                write!(f, "unexpected response {}: {}", status, first_line)
+            }
+            SubmitError::Http(e) => write!(f, "request failed: {}", e),
+            SubmitError::Io { path, source } => {
+                write!(f, "couldn't access {}: {}", path.display(), source)
+            }
+            SubmitError::History {
+                path,
+                line,
+                message,
+            } => write!(f, "{}, line {}: {}", path.display(), line, message),
+            SubmitError::AlreadyGuessed { answer, verdict } => {
+                write!(f, "{} was already submitted, and was {}", answer, verdict)
+            }
+            SubmitError::Implausible {
+                answer,
+                bound,
+                verdict,
+            } => write!(
+                f,
+                "{} was already {}, so {} probably is too, pass --force to submit it anyway",
+                bound, verdict, answer
+            ),
+            SubmitError::Rejected(Response::Wait(Some(wait))) => {
+                write!(f, "answered too recently, try again in {:?}", wait)
+            }
+            SubmitError::Rejected(Response::Wait(None)) => {
+                write!(f, "answered too recently, try again later")
+            }
+            SubmitError::Rejected(Response::WrongLevel) => {
+                write!(f, "that part is already solved, or isn't unlocked yet")
+            }
+            SubmitError::Rejected(Response::Unrecognized(text)) => {
+                write!(f, "unrecognized response: {}", text)
+            }
+            SubmitError::Rejected(Response::Verdict(verdict)) => {
+                write!(f, "unexpected verdict: {}", verdict)
+            }
+            SubmitError::Output(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::io::{BufRead, BufReader, Read};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use crate::fetch::Ureq;
+
+    use super::*;
+
+    fn page(article: &str) -> String {
+        format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            article
+        )
+    }
+
+    #[test]
+    fn parses_responses() {
+        let correct = page("That's the right answer!  You are <em>one gold star</em> closer.");
+        assert_eq!(
+            parse_response(&correct),
+            Response::Verdict(Verdict::Correct)
+        );
+
+        let high = page(
+            "That's not the right answer; your answer is too high.  If you're stuck, [...] \
+             Please wait one minute before trying again. <a href=\"/2021/day/1\">[Return]</a>",
+        );
+        assert_eq!(parse_response(&high), Response::Verdict(Verdict::TooHigh));
+        let low = page("That's not the right answer; your answer is too low.");
+        assert_eq!(parse_response(&low), Response::Verdict(Verdict::TooLow));
+        let wrong = page("That's not the right answer.  If you're stuck, make sure...");
+        assert_eq!(parse_response(&wrong), Response::Verdict(Verdict::Wrong));
+
+        let wait = page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.");
+        assert_eq!(
+            parse_response(&wait),
+            Response::Wait(Some(Duration::from_secs(65)))
+        );
+        let wait = page("You gave an answer too recently; You have 38s left to wait.");
+        assert_eq!(
+            parse_response(&wait),
+            Response::Wait(Some(Duration::from_secs(38)))
+        );
+
+        let level =
+            page("You don't seem to be solving the right level.  Did you already complete it?");
+        assert_eq!(parse_response(&level), Response::WrongLevel);
+        assert_eq!(
+            parse_response("<p>Something <b>else</b></p>"),
+            Response::Unrecognized("Something else".to_string())
+        );
+    }
+
+    fn guess(part: u8, answer: &str, verdict: Verdict) -> Guess {
+        Guess {
+            part,
+            answer: answer.to_string(),
+            verdict,
+        }
+    }
+
+    #[test]
+    fn checks_against_earlier_guesses() {
+        let dir = std::env::temp_dir().join(format!("advent-guesses-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut history = History::load(&dir, 2021, 7).unwrap();
+        assert!(history.guesses().is_empty());
+        assert_eq!(history.check(Part::One, "100"), Check::Plausible);
+
+        history.record(guess(1, "500", Verdict::TooHigh)).unwrap();
+        history.record(guess(1, "900", Verdict::TooHigh)).unwrap();
+        history.record(guess(1, "20", Verdict::TooLow)).unwrap();
+        history.record(guess(1, "77", Verdict::Wrong)).unwrap();
+        history.record(guess(2, "x", Verdict::Correct)).unwrap();
+
+        assert_eq!(
+            history.check(Part::One, "900"),
+            Check::AlreadyGuessed(Verdict::TooHigh)
+        );
+        assert_eq!(
+            history.check(Part::One, "77"),
+            Check::AlreadyGuessed(Verdict::Wrong)
+        );
+        assert_eq!(
+            history.check(Part::One, "600"),
+            Check::AboveBound("500".to_string())
+        );
+        assert_eq!(
+            history.check(Part::One, "-3"),
+            Check::BelowBound("20".to_string())
+        );
+        assert_eq!(history.check(Part::One, "499"), Check::Plausible);
+        assert_eq!(history.check(Part::One, "abc"), Check::Plausible);
+        assert_eq!(
+            history.check(Part::Two, "y"),
+            Check::AlreadySolved("x".to_string())
+        );
+
+        let reloaded = History::load(&dir, 2021, 7).unwrap();
+        assert_eq!(reloaded, history);
+        assert!(History::load(&dir, 2022, 7).unwrap().guesses().is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    /// Answers every submission with the next of a list of pages, remembering what was submitted
+    struct Site {
+        pages: RefCell<Vec<String>>,
+        answers: RefCell<Vec<String>>,
+    }
+
+    impl Http for &Site {
+        fn get(&self, _: &str, _: &[(&str, &str)]) -> Result<(u16, String), String> {
+            Err("submitting never gets".to_string())
+        }
+
+        fn post_form(
+            &self,
+            _: &str,
+            _: &[(&str, &str)],
+            form: &[(&str, &str)],
+        ) -> Result<(u16, String), String> {
+            self.answers.borrow_mut().push(form[1].1.to_string());
+            let page = self.pages.borrow_mut().remove(0);
+            Ok((200, page))
+        }
+    }
+
+    #[test]
+    fn only_submits_plausible_answers() {
+        let dir = std::env::temp_dir().join(format!("advent-submit-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut history = History::load(&dir, 2021, 7).unwrap();
+        history.record(guess(1, "500", Verdict::TooHigh)).unwrap();
+        let site = Site {
+            pages: RefCell::new(vec![
+                page("You gave an answer too recently; You have 38s left to wait."),
+                page("That's not the right answer; your answer is too low."),
+                page("That's the right answer!"),
+            ]),
+            answers: RefCell::new(vec![]),
+        };
+        let submitter = Submitter::new(&site, "abc".into());
+        let mut submit = |answer, force| {
+            let mut out = vec![];
+            let result = submitter.submit_checked(&mut out, &mut history, Part::One, answer, force);
+            (result, String::from_utf8(out).unwrap())
+        };
+
+        let (result, out) = submit("600", false);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "500 was already too high, so 600 probably is too, pass --force to submit it anyway"
+        );
+        assert!(out.is_empty());
+
+        let (result, _) = submit("40", false);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "answered too recently, try again in 38s"
+        );
+        let (result, out) = submit("40", false);
+        assert!(!result.unwrap());
+        assert_eq!(out, "submitting 40 for 2021 day 7 part 1\ntoo low\n");
+        let (result, _) = submit("40", false);
+        assert!(matches!(
+            result,
+            Err(SubmitError::AlreadyGuessed {
+                verdict: Verdict::TooLow,
+                ..
+            })
+        ));
+
+        assert!(submit("600", true).0.unwrap());
+        let (result, out) = submit("41", false);
+        assert!(!result.unwrap());
+        assert_eq!(out, "already solved with 600\n");
+
+        assert_eq!(*site.answers.borrow(), ["40", "40", "600"]);
+        assert_eq!(
+            History::load(&dir, 2021, 7).unwrap().check(Part::One, "1"),
+            Check::AlreadySolved("600".to_string())
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn submits_over_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = vec![];
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push(line.trim().to_string());
+            }
+            let length: usize = head
+                .iter()
+                .find_map(|line| line.strip_prefix("Content-Length: "))
+                .unwrap()
+                .parse()
+                .unwrap();
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+
+            let response = page("That's not the right answer; your answer is too low.");
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                response.len(),
+                response
+            )
+            .unwrap();
+            (head, String::from_utf8(body).unwrap())
+        });
+
+        let submitter = Submitter::new(Ureq::new(), "abc".into()).endpoint(endpoint);
+        assert_eq!(
+            submitter.submit(2021, 9, Part::Two, "1134").unwrap(),
+            Response::Verdict(Verdict::TooLow)
+        );
+
+        let (head, body) = server.join().unwrap();
+        assert_eq!(head[0], "POST /2021/day/9/answer HTTP/1.1");
+        assert!(head.iter().any(|line| line == "Cookie: session=abc"));
+        assert_eq!(body, "level=2&answer=1134");
+    }
+}
@@ -1,18 +1,24 @@
 #[macro_export]
 macro_rules! input_const {
-    ($path:literal) => {{
-        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/inputs/input", $path))
+    ($year:literal, $day:literal) => {{
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/inputs/",
+            $year,
+            "/input",
+            $day
+        ))
     }};
 }
 
 #[macro_export]
 macro_rules! input_lines {
-    ($path:literal) => {{
-        $crate::input_const!($path)
+    ($year:literal, $day:literal) => {{
+        $crate::input_const!($year, $day)
             .lines()
     }};
 }
 
 #[cfg(test)]
-const CHECK_INPUT_CONST: &str = input_const!("1");
-
+// fails to compile if the macro can't find the inputs
+const _: &str = input_const!(2021, 1);
@@ -0,0 +1,457 @@
+//! Drawing grid-based days in the terminal: a day's state becomes a [`Frame`] of coloured
+//! characters, and a simulation becomes a sequence of them, which a [`Player`] animates. So far
+//! only 2021's grid days are covered, so the drawings are named after their 2021 day.
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
+
+use crate::grid::Grid;
+use crate::parse::ParseError;
+use crate::y2021::{day11, day13, day15, day5, day9};
+
+/// A colour, as red, green and blue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GREY: Rgb = Rgb(90, 90, 90);
+
+    /// Somewhere between `self` (at 0) and `other` (at 1)
+    fn mix(self, other: Rgb, t: f64) -> Rgb {
+        let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Rgb(
+            channel(self.0, other.0),
+            channel(self.1, other.1),
+            channel(self.2, other.2),
+        )
+    }
+}
+
+/// A colour from dark blue (at 0) through purple and orange to yellow (at 1), for showing how
+/// big a number is
+pub fn heat(t: f64) -> Rgb {
+    const STOPS: [Rgb; 4] = [
+        Rgb(20, 30, 80),
+        Rgb(130, 40, 140),
+        Rgb(235, 110, 40),
+        Rgb(250, 230, 90),
+    ];
+    let t = t.clamp(0.0, 1.0) * (STOPS.len() - 1) as f64;
+    let i = (t as usize).min(STOPS.len() - 2);
+    STOPS[i].mix(STOPS[i + 1], t - i as f64)
+}
+
+/// The `i`th of a series of colours that are easy to tell apart
+pub fn distinct(i: usize) -> Rgb {
+    // stepping round the colour wheel by the golden angle never repeats a hue
+    let hue = (i as f64 * 137.508) % 360.0;
+    let sector = hue / 60.0;
+    let x = 1.0 - (sector % 2.0 - 1.0).abs();
+    let (r, g, b) = match sector as u32 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    let channel = |c: f64| (80.0 + c * 160.0) as u8;
+    Rgb(channel(r), channel(g), channel(b))
+}
+
+/// One character of a frame, and its colours
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub fg: Option<Rgb>,
+    pub bg: Option<Rgb>,
+}
+
+impl Cell {
+    /// `glyph` in the terminal's own colours
+    pub fn plain(glyph: char) -> Self {
+        Self {
+            glyph,
+            fg: None,
+            bg: None,
+        }
+    }
+
+    pub fn fg(glyph: char, fg: Rgb) -> Self {
+        Self {
+            fg: Some(fg),
+            ..Self::plain(glyph)
+        }
+    }
+
+    pub fn bg(glyph: char, fg: Rgb, bg: Rgb) -> Self {
+        Self {
+            bg: Some(bg),
+            ..Self::fg(glyph, fg)
+        }
+    }
+}
+
+/// A single picture of a day's state, with a line saying what it shows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub cells: Grid<Cell>,
+    pub caption: String,
+}
+
+impl Frame {
+    pub fn new(cells: Grid<Cell>, caption: impl Into<String>) -> Self {
+        Self {
+            cells,
+            caption: caption.into(),
+        }
+    }
+
+    /// The frame as lines of text, with ANSI escape codes for the colours if `color` is set,
+    /// followed by the caption
+    pub fn to_ansi(&self, color: bool) -> String {
+        let mut s = String::new();
+        for row in self.cells.rows().take(self.cells.height()) {
+            let mut current = (None, None);
+            for cell in row {
+                let colors = (cell.fg, cell.bg);
+                // only change colour between runs of differently coloured cells
+                if color && colors != current {
+                    s.push_str("\x1b[0m");
+                    if let Some(Rgb(r, g, b)) = cell.fg {
+                        write!(s, "\x1b[38;2;{};{};{}m", r, g, b).unwrap();
+                    }
+                    if let Some(Rgb(r, g, b)) = cell.bg {
+                        write!(s, "\x1b[48;2;{};{};{}m", r, g, b).unwrap();
+                    }
+                    current = colors;
+                }
+                s.push(cell.glyph);
+            }
+            if color && current != (None, None) {
+                s.push_str("\x1b[0m");
+            }
+            s.push('\n');
+        }
+        s.push_str(&self.caption);
+        s.push('\n');
+        s
+    }
+}
+
+/// Draws a day's input: a single frame, or one for each step of a simulation
+pub type Visualization = fn(&str) -> Result<Frames, ParseError>;
+
+/// Frames are drawn as they're played, since simulations can have a lot of them
+pub type Frames = Box<dyn Iterator<Item = Frame>>;
+
+pub fn visualization(year: u16, day: u8) -> Option<Visualization> {
+    let visualization: Visualization = match (year, day) {
+        (2021, 5) => day5,
+        (2021, 9) => day9,
+        (2021, 11) => day11,
+        (2021, 13) => day13,
+        (2021, 15) => day15,
+        _ => return None,
+    };
+    Some(visualization)
+}
+
+/// Plays frames one after another
+pub struct Player<W> {
+    out: W,
+    /// How long to show each frame, or `None` to print them all one after another
+    delay: Option<Duration>,
+    color: bool,
+}
+
+impl<W: Write> Player<W> {
+    /// Print every frame in colour, one after another
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            delay: None,
+            color: true,
+        }
+    }
+
+    /// Animate the frames in place, showing this many a second
+    pub fn fps(mut self, fps: f64) -> Self {
+        self.delay = Duration::try_from_secs_f64(1.0 / fps).ok();
+        self
+    }
+
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Show each frame in turn, returning how many there were
+    pub fn play(&mut self, frames: impl IntoIterator<Item = Frame>) -> io::Result<usize> {
+        let animate = self.delay.is_some();
+        if animate {
+            // clear the screen and hide the cursor
+            write!(self.out, "\x1b[2J\x1b[?25l")?;
+        }
+        let result = self.play_frames(frames);
+        if animate {
+            write!(self.out, "\x1b[?25h")?;
+        }
+        self.out.flush()?;
+        result
+    }
+
+    fn play_frames(&mut self, frames: impl IntoIterator<Item = Frame>) -> io::Result<usize> {
+        let mut count = 0;
+        for frame in frames {
+            match self.delay {
+                Some(delay) => {
+                    if count > 0 {
+                        thread::sleep(delay);
+                    }
+                    // back to the top left, drawing over the last frame
+                    write!(self.out, "\x1b[H{}\x1b[J", frame.to_ansi(self.color))?;
+                    self.out.flush()?;
+                }
+                None => {
+                    if count > 0 {
+                        writeln!(self.out)?;
+                    }
+                    write!(self.out, "{}", frame.to_ansi(self.color))?;
+                }
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+/// How many vents overlap at each point, with only the straight lines and then with every line
+pub fn day5(input: &str) -> Result<Frames, ParseError> {
+    let lines = day5::parse_lines(input)?;
+    let straight: Vec<_> = lines
+        .iter()
+        .filter(|line| !line.is_diagonal())
+        .copied()
+        .collect();
+    let frames = [(straight, "straight lines"), (lines, "every line")].map(|(lines, which)| {
+        let (width, height) = day5::max_dimensions(&lines);
+        let counts = day5::model::new_counts(width, height);
+        day5::model::apply_lines(&counts, lines.clone());
+        let counts = counts.map(|count| count.load(Ordering::Relaxed));
+        let most = counts.iter().copied().max().unwrap_or(0).max(1);
+        let cells = counts.map(|&count| match count {
+            0 => Cell::fg('.', Rgb::GREY),
+            1..=9 => Cell::fg(
+                char::from_digit(count as u32, 10).unwrap(),
+                heat(count as f64 / most as f64),
+            ),
+            _ => Cell::fg('+', heat(1.0)),
+        });
+        let overlaps = counts.iter().filter(|&&count| count >= 2).count();
+        Frame::new(cells, format!("{}: {} overlaps", which, overlaps))
+    });
+    Ok(Box::new(frames.into_iter()))
+}
+
+/// The heightmap, each basin in its own colour with its low point highlighted
+pub fn day9(input: &str) -> Result<Frames, ParseError> {
+    let heights = day9::parse_heights(input)?;
+    let mut basin = Grid::new(heights.width(), heights.height(), None);
+    let basins = day9::basins(&heights);
+    for (i, positions) in basins.iter().enumerate() {
+        for &position in positions {
+            basin[position] = Some(i);
+        }
+    }
+
+    let cells = Grid::from_fn(heights.width(), heights.height(), |x, y| {
+        let glyph = char::from_digit(heights[(x, y)] as u32, 10).unwrap();
+        match basin[(x, y)] {
+            None => Cell::fg(glyph, Rgb::GREY),
+            Some(_) if day9::is_low_point(&heights, x, y) => Cell::bg('*', Rgb::BLACK, Rgb::WHITE),
+            Some(i) => Cell::fg(glyph, distinct(i)),
+        }
+    });
+    let caption = format!(
+        "{} basins, the largest three multiplying to {}",
+        basins.len(),
+        day9::largest_basins_product(&heights)
+    );
+    Ok(Box::new(std::iter::once(Frame::new(cells, caption))))
+}
+
+/// Never animate more steps than this, since some grids never synchronize
+const MAX_OCTOPUS_STEPS: usize = 1000;
+
+/// The octopuses' energy levels at each step, until they all flash at once
+pub fn day11(input: &str) -> Result<Frames, ParseError> {
+    let mut octopuses = day11::parse_octopuses(input)?;
+    let draw = |octopuses: &Grid<u32>, caption| {
+        let cells = octopuses.map(|&energy| match energy {
+            0 => Cell::bg('0', Rgb::BLACK, Rgb::WHITE),
+            _ => Cell::fg(
+                char::from_digit(energy, 10).unwrap(),
+                heat(energy as f64 / 9.0),
+            ),
+        });
+        Frame::new(cells, caption)
+    };
+
+    let first = draw(&octopuses, "step 0".to_string());
+    let mut total = 0;
+    let mut synchronized = false;
+    let steps = (1..=MAX_OCTOPUS_STEPS).map_while(move |i| {
+        if synchronized {
+            return None;
+        }
+        let flashes = day11::step(&mut octopuses);
+        total += flashes;
+        synchronized = flashes == octopuses.width() * octopuses.height();
+        let caption = format!("step {}: {} flashes, {} in total", i, flashes, total);
+        Some(draw(&octopuses, caption))
+    });
+    Ok(Box::new(std::iter::once(first).chain(steps)))
+}
+
+/// The paper before folding, then after each fold
+pub fn day13(input: &str) -> Result<Frames, ParseError> {
+    let (paper, folds) = day13::parse_input(input)?;
+    let draw = |paper: &day13::Paper, caption| {
+        let cells = paper.map(|&dot| {
+            if dot {
+                Cell::fg('#', heat(1.0))
+            } else {
+                Cell::fg('.', Rgb::GREY)
+            }
+        });
+        Frame::new(cells, caption)
+    };
+
+    let first = draw(&paper, format!("{} dots", day13::dots(&paper)));
+    let count = folds.len();
+    let folded = folds
+        .into_iter()
+        .enumerate()
+        .scan(paper, move |paper, (i, fold)| {
+            *paper = day13::apply_fold(paper, &fold);
+            let caption = format!("fold {} of {}: {} dots", i + 1, count, day13::dots(paper));
+            Some(draw(paper, caption))
+        });
+    Ok(Box::new(std::iter::once(first).chain(folded)))
+}
+
+/// The risk levels with the safest path through them, first for the tile and then for the whole
+/// cave
+pub fn day15(input: &str) -> Result<Frames, ParseError> {
+    let tile = day15::parse_risk_levels(input)?;
+    let draw = |grid: &Grid<u64>, which| {
+        let (path, risk) = day15::safest_path(grid);
+        let mut cells = grid.map(|&risk| {
+            Cell::fg(
+                char::from_digit(risk as u32, 10).unwrap(),
+                heat((risk - 1) as f64 / 8.0).mix(Rgb::BLACK, 0.4),
+            )
+        });
+        for position in path {
+            cells[position] = Cell::bg(cells[position].glyph, Rgb::BLACK, Rgb::WHITE);
+        }
+        Frame::new(cells, format!("{}: total risk {}", which, risk))
+    };
+    let frames = [
+        draw(&tile, "the tile"),
+        draw(&day15::expand(&tile), "the whole cave"),
+    ];
+    Ok(Box::new(frames.into_iter()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_runs_of_cells() {
+        let red = Rgb(255, 0, 0);
+        let cells = Grid::from_fn(3, 2, |x, y| match (x, y) {
+            (0, 0) | (1, 0) => Cell::fg('#', red),
+            (0, 1) => Cell::bg('*', Rgb::BLACK, Rgb::WHITE),
+            _ => Cell::plain('.'),
+        });
+        let frame = Frame::new(cells, "two rows");
+        assert_eq!(frame.to_ansi(false), "##.\n*..\ntwo rows\n");
+        assert_eq!(
+            frame.to_ansi(true),
+            "\x1b[0m\x1b[38;2;255;0;0m##\x1b[0m.\n\
+             \x1b[0m\x1b[38;2;0;0;0m\x1b[48;2;255;255;255m*\x1b[0m..\n\
+             two rows\n"
+        );
+    }
+
+    #[test]
+    fn picks_colors() {
+        assert_eq!(heat(0.0), Rgb(20, 30, 80));
+        assert_eq!(heat(1.0), Rgb(250, 230, 90));
+        assert_eq!(heat(2.0), heat(1.0));
+        let colors: std::collections::HashSet<_> = (0..20).map(distinct).collect();
+        assert_eq!(colors.len(), 20);
+    }
+
+    #[test]
+    fn plays_frames() {
+        let frame = |caption: &str| Frame::new(Grid::new(2, 1, Cell::plain('#')), caption);
+        let mut out = vec![];
+        let count = Player::new(&mut out)
+            .color(false)
+            .play([frame("one"), frame("two")])
+            .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(String::from_utf8(out).unwrap(), "##\none\n\n##\ntwo\n");
+
+        let mut out = vec![];
+        Player::new(&mut out)
+            .fps(1000.0)
+            .color(false)
+            .play([frame("one"), frame("two")])
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b[2J\x1b[?25l\x1b[H##\none\n\x1b[J\x1b[H##\ntwo\n\x1b[J\x1b[?25h"
+        );
+    }
+
+    #[test]
+    fn draws_the_days() {
+        let octopuses = "5483143223\n2745854711\n5264556173\n6141336146\n6357385478\n\
+                         4167524645\n2176841721\n6882881134\n4846848554\n5283751526\n";
+        let frames: Vec<_> = visualization(2021, 11).unwrap()(octopuses)
+            .unwrap()
+            .collect();
+        // the given example first synchronizes on step 195
+        assert_eq!(frames.len(), 196);
+        assert_eq!(frames[2].caption, "step 2: 35 flashes, 35 in total");
+        assert!(frames[195].cells.iter().all(|cell| cell.glyph == '0'));
+
+        let paper = "0,0\n1,0\n4,0\n0,2\n\nfold along y=1\nfold along x=2\n";
+        let frames: Vec<_> = day13(paper).unwrap().collect();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].caption, "4 dots");
+        assert_eq!(frames[2].to_ansi(false), "##\nfold 2 of 2: 2 dots\n");
+
+        let risks = "116\n138\n213\n";
+        let frames: Vec<_> = day15(risks).unwrap().collect();
+        assert_eq!(
+            frames[0].to_ansi(false),
+            "116\n138\n213\nthe tile: total risk 7\n"
+        );
+        assert_eq!(frames[0].cells[(0, 1)].bg, Some(Rgb::WHITE));
+        assert_eq!(frames[0].cells[(2, 0)].bg, None);
+
+        assert!(visualization(2021, 1).is_none());
+        assert!(day9("12\n3").is_err());
+    }
+}
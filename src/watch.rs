@@ -0,0 +1,200 @@
+//! Re-running a day whenever its input changes. Files are polled rather than watched with
+//! inotify and friends, which is plenty for a handful of files and works anywhere.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::input::InputSource;
+use crate::report::on_own_line;
+use crate::runner::{self, Day, Failure, Part};
+
+/// What a file looked like when it was last checked, or `None` if it didn't exist
+type Stamp = Option<(Option<SystemTime>, u64)>;
+
+fn stamp(path: &Path) -> Stamp {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.modified().ok(), meta.len()))
+}
+
+/// Polls some files for changes to their modification time or length
+#[derive(Debug)]
+pub struct Watcher {
+    files: Vec<(PathBuf, Stamp)>,
+}
+
+impl Watcher {
+    /// Watch `paths`, which don't have to exist yet, for changes from how they are now
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let stamp = stamp(&path);
+                (path, stamp)
+            })
+            .collect();
+        Self { files }
+    }
+
+    /// The files that have changed, appeared or disappeared since the last check
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = vec![];
+        for (path, last) in &mut self.files {
+            let now = stamp(path);
+            if now != *last {
+                *last = now;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+
+    /// Check every `interval` until something changes, then return what did
+    pub fn wait(&mut self, interval: Duration) -> Vec<PathBuf> {
+        loop {
+            let changed = self.changed();
+            if !changed.is_empty() {
+                return changed;
+            }
+            thread::sleep(interval);
+        }
+    }
+}
+
+/// How a part's result compares with the last run's
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diff {
+    /// The part hasn't been run before
+    New,
+    Unchanged,
+    /// Different from last time, which was this
+    Changed(Result<String, Failure>),
+}
+
+/// The latest result of each part, to compare each run with the one before
+#[derive(Debug, Clone, Default)]
+pub struct Latest {
+    results: Vec<(Part, Result<String, Failure>)>,
+}
+
+impl Latest {
+    /// Remember `result` as `part`'s latest, and how it differs from the one before
+    pub fn update(&mut self, part: Part, result: &Result<String, Failure>) -> Diff {
+        match self.results.iter_mut().find(|(p, _)| *p == part) {
+            None => {
+                self.results.push((part, result.clone()));
+                Diff::New
+            }
+            Some((_, last)) if last == result => Diff::Unchanged,
+            Some((_, last)) => Diff::Changed(std::mem::replace(last, result.clone())),
+        }
+    }
+}
+
+/// Run the day once, writing each part's result and how it compares with the last run's
+pub fn run(
+    out: &mut impl Write,
+    day: &Day,
+    parts: &[Part],
+    source: &InputSource,
+    limit: Option<Duration>,
+    latest: &mut Latest,
+) -> io::Result<()> {
+    let input = match source.load(day.year, day.number) {
+        Ok(input) => input,
+        Err(e) => return writeln!(out, "{}: {}", day, e),
+    };
+    let run = runner::run_day(day, &input, parts, limit);
+    let outcomes = match run.result {
+        Ok(outcomes) => outcomes,
+        Err(failure) => return writeln!(out, "{}: {}", day, failure),
+    };
+
+    writeln!(out, "{}: parsed in {:?}", day, run.parse_time)?;
+    for outcome in outcomes {
+        let diff = match latest.update(outcome.part, &outcome.result) {
+            Diff::New => String::new(),
+            Diff::Unchanged => " [unchanged]".to_string(),
+            Diff::Changed(Ok(before)) => format!(" [was {}]", on_own_line(&before)),
+            Diff::Changed(Err(Failure::Panic(_))) => " [panicked before]".to_string(),
+            Diff::Changed(Err(Failure::Timeout(_))) => " [timed out before]".to_string(),
+        };
+        let result = match outcome.result {
+            Ok(answer) => on_own_line(&answer),
+            Err(failure) => failure.to_string(),
+        };
+        writeln!(
+            out,
+            "{} part {}: {} ({:?}){}",
+            day,
+            outcome.part.number(),
+            result,
+            outcome.elapsed,
+            diff
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notices_changes() {
+        let path = std::env::temp_dir().join(format!("advent-watch-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut watcher = Watcher::new([path.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        fs::write(&path, "1\n2\n").unwrap();
+        assert_eq!(watcher.changed(), vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        fs::write(&path, "1\n2\n3\n").unwrap();
+        assert_eq!(watcher.wait(Duration::from_millis(1)), vec![path.clone()]);
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(watcher.changed(), vec![path]);
+    }
+
+    #[test]
+    fn compares_with_the_last_run() {
+        let mut latest = Latest::default();
+        assert_eq!(latest.update(Part::One, &Ok("5".into())), Diff::New);
+        assert_eq!(latest.update(Part::Two, &Ok("7".into())), Diff::New);
+        assert_eq!(latest.update(Part::One, &Ok("5".into())), Diff::Unchanged);
+        assert_eq!(
+            latest.update(Part::One, &Err(Failure::Panic("oh no".into()))),
+            Diff::Changed(Ok("5".into()))
+        );
+        assert_eq!(
+            latest.update(Part::One, &Ok("6".into())),
+            Diff::Changed(Err(Failure::Panic("oh no".into())))
+        );
+        assert_eq!(latest.update(Part::Two, &Ok("7".into())), Diff::Unchanged);
+    }
+
+    #[test]
+    fn shows_what_changed() {
+        let path = std::env::temp_dir().join(format!("advent-watch-run-{}", std::process::id()));
+        let day = runner::find_day(2021, 1).unwrap();
+        let source = InputSource::File(path.clone());
+        let mut latest = Latest::default();
+        let mut run_on = |input: &str| {
+            fs::write(&path, input).unwrap();
+            let mut out = vec![];
+            run(&mut out, day, &[Part::One], &source, None, &mut latest).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        assert!(run_on("1\n2\n3").contains("2021 day 1 part 1: 2 ("));
+        assert!(run_on("1\n2\n3").ends_with(") [unchanged]\n"));
+        assert!(run_on("3\n2\n1").ends_with(") [was 2]\n"));
+        assert!(run_on("x").starts_with("2021 day 1: invalid input: "));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
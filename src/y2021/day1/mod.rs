@@ -0,0 +1,84 @@
+use crate::parse::{ParseError, Span};
+use crate::solution::Solution;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i32>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<i32>) -> usize {
+        count_increases(input)
+    }
+
+    fn part2(input: &Vec<i32>) -> usize {
+        let summed = summed_list(input);
+        count_increases(&summed)
+    }
+}
+
+/// How many measurements are larger than the one before
+pub fn count_increases(i: &[i32]) -> usize {
+    i.windows(2).filter(|w| w[0] < w[1]).count()
+}
+
+/// The sum of each three-measurement sliding window
+pub fn summed_list(i: &[i32]) -> Vec<i32> {
+    i.windows(3).map(|w| w[0] + w[1] + w[2]).collect()
+}
+
+pub fn parse_input(s: &str) -> Result<Vec<i32>, ParseError> {
+    Span::lines(s).map(|line| line.parse("a number")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::input_const;
+
+    use super::*;
+
+    #[test]
+    fn summed_list_test() {
+        let x = summed_list(&[1, 2, 3]);
+        assert_eq!(x, vec![6]);
+
+        assert_eq!(summed_list(&[]), [0; 0]);
+        assert_eq!(summed_list(&[1]), [0; 0]);
+        assert_eq!(summed_list(&[1, 2]), [0; 0]);
+    }
+
+    #[test]
+    fn parses_input() {
+        let i = parse_input(input_const!(2021, 1)).unwrap();
+        assert_eq!(i.first(), Some(&173));
+        assert_eq!(i.last(), Some(&9380));
+    }
+
+    #[test]
+    fn rejects_bad_numbers() {
+        let err = parse_input("1\n2\nthree\n4").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.found, r#""three""#);
+    }
+
+    #[test]
+    fn given_example() {
+        assert_eq!(
+            count_increases(&[199, 200, 208, 210, 200, 207, 240, 269, 260, 263]),
+            7
+        );
+    }
+
+    #[test]
+    fn edge_cases() {
+        assert_eq!(count_increases(&[]), 0);
+        assert_eq!(count_increases(&[1]), 0);
+        assert_eq!(count_increases(&[1, 0]), 0);
+    }
+}
@@ -0,0 +1,189 @@
+mod fast;
+
+use crate::parse::{ParseError, Span};
+use crate::solution::Solution;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Vec<String>) -> u64 {
+        syntax_error_score(input)
+    }
+
+    fn part2(input: &Vec<String>) -> u64 {
+        middle_completion_score(input)
+    }
+}
+
+/// The non-empty lines, which must be made of nothing but brackets
+pub fn parse_lines(s: &str) -> Result<Vec<String>, ParseError> {
+    Span::lines(s)
+        .filter(|s| !s.is_empty())
+        .map(|line| {
+            match line.chars().find(|(_, c)| !"()[]{}<>".contains(*c)) {
+                Some((c, _)) => Err(c.error("a bracket")),
+                None => Ok(line.as_str().to_owned()),
+            }
+        })
+        .collect()
+}
+
+fn inverse(s: char) -> char {
+    match s {
+        ')' => '(',
+        ']' => '[',
+        '}' => '{',
+        '>' => '<',
+        '(' => ')',
+        '[' => ']',
+        '{' => '}',
+        '<' => '>',
+        s => unreachable!("{}", s),
+    }
+}
+
+fn is_opening(s: char) -> bool {
+    match s {
+        '{' | '[' | '(' | '<' => true,
+        '}' | ']' | ')' | '>' => false,
+        s => unreachable!("{}", s),
+    }
+}
+
+fn score(s: char) -> u64 {
+    match s {
+        ')' => 3,
+        ']' => 57,
+        '}' => 1197,
+        '>' => 25137,
+        s => unreachable!("{}", s),
+    }
+}
+
+/// The score of the first illegal character, if the line is corrupted
+pub fn score_for_line(s: &str) -> Option<u64> {
+    let mut v = vec![];
+
+    for c in s.chars() {
+        if is_opening(c) {
+            v.push(c);
+        } else if v.pop().map(inverse) != Some(c) {
+            return Some(score(c));
+        }
+    }
+
+    None
+}
+
+pub fn syntax_error_score(lines: &[String]) -> u64 {
+    lines.iter().filter_map(|s| score_for_line(s)).sum()
+}
+
+/// The closing characters that complete an incomplete line, in order
+pub fn fix_line(s: &str) -> impl Iterator<Item = char> {
+    let mut v = vec![];
+    for c in s.chars() {
+        if is_opening(c) {
+            v.push(c);
+        } else {
+            v.pop().unwrap();
+        }
+    }
+
+    v.into_iter().map(inverse).rev()
+}
+
+/// The middle score of completing each incomplete line, ignoring corrupted ones
+pub fn middle_completion_score(lines: &[String]) -> u64 {
+    let mut scores: Vec<_> = lines
+        .iter()
+        .filter(|s| is_incomplete(s))
+        .map(|s| score_for_line2(s))
+        .collect();
+
+    scores.sort();
+    let middle = (scores.len() - 1) / 2;
+    scores[middle]
+    
+}
+
+fn is_incomplete(s: &str) -> bool {
+    score_for_line(s).is_none()
+}
+
+fn score_for_line2(s: &str) -> u64 {
+    let mut i = 0;
+    for c in fix_line(s) {
+        i *= 5;
+        i += match c {
+            ')' => 1,
+            ']' => 2,
+            '}' => 3,
+            '>' => 4,
+            s => unreachable!("{}", s),
+        };
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calculate1(s: &str) -> u64 {
+        syntax_error_score(&parse_lines(s).unwrap())
+    }
+
+    fn calculate2(s: &str) -> u64 {
+        middle_completion_score(&parse_lines(s).unwrap())
+    }
+
+    const GIVEN_INPUT: &str = r#"[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]"#;
+
+    #[test]
+    fn test_given_input() {
+        assert_eq!(calculate1(GIVEN_INPUT), 26397);
+    }
+
+    #[test]
+    fn check_fix_line() {
+        assert_eq!(
+            fix_line("<{([{{}}[<[[[<>{}]]]>[]]").collect::<Vec<_>>(),
+            vec![']', ')', '}', '>']
+        );
+    }
+
+    #[test]
+    fn test_given_input2() {
+        assert_eq!(calculate2(GIVEN_INPUT), 288957);
+    }
+
+    #[test]
+    fn rejects_non_brackets() {
+        let err = Day10::parse("[()]\n\n{<a>}").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+        assert_eq!(err.found, "\"a\"");
+    }
+
+    #[test]
+    fn unmatched_closer_is_corrupt() {
+        assert_eq!(score_for_line(")"), Some(3));
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::HashSet;
+
+use crate::grid::Grid;
+use crate::parse::{ParseError, Span};
+use crate::runner;
+use crate::solution::Solution;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Grid<u32>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<u32>, ParseError> {
+        parse_octopuses(input)
+    }
+
+    fn part1(input: &Grid<u32>) -> usize {
+        total_flashes(input.clone(), 100)
+    }
+
+    fn part2(input: &Grid<u32>) -> usize {
+        first_synchronized_step(input.clone())
+    }
+}
+
+const SIZE: usize = 10;
+
+/// Exactly 10 rows of 10 digits
+pub fn parse_octopuses(s: &str) -> Result<Grid<u32>, ParseError> {
+    let grid = Grid::parse_digits(s, "energy level", 0..=9)?;
+
+    let mut rows = Span::lines(s).filter(|line| !line.is_empty());
+    let first = rows.next().unwrap();
+    if grid.width() < SIZE {
+        return Err(first.end_error("10 octopuses in each row"));
+    }
+    if let Some((c, _)) = first.chars().nth(SIZE) {
+        return Err(c.error("end of line"));
+    }
+    if grid.height() < SIZE {
+        return Err(ParseError::end_of_input(s, "10 rows of octopuses"));
+    }
+    if let Some(extra) = rows.nth(SIZE - 1) {
+        return Err(extra.error("end of input"));
+    }
+
+    Ok(grid)
+}
+
+/// Advance one step, returning how many octopuses flashed
+pub fn step(grid: &mut Grid<u32>) -> usize {
+    for i in grid.iter_mut() {
+        *i += 1;
+    }
+
+    let mut flashed = HashSet::new();
+
+    loop {
+        let new_flashes: HashSet<_> = grid
+            .positions()
+            .filter(|&(x, y)| grid[(x, y)] > 9 && !flashed.contains(&(x, y)))
+            .collect();
+        for &(x, y) in &new_flashes {
+            for neighbor in grid.neighbors8(x, y) {
+                grid[neighbor] += 1;
+            }
+        }
+        if new_flashes.is_empty() {
+            break;
+        } else {
+            flashed.extend(new_flashes);
+        }
+    }
+
+    for &position in &flashed {
+        grid[position] = 0;
+    }
+
+    flashed.len()
+}
+
+/// How many flashes there are in `steps` steps
+pub fn total_flashes(mut grid: Grid<u32>, steps: usize) -> usize {
+    (0..steps).map(|_| step(&mut grid)).sum()
+}
+
+/// Whether all the octopuses flash at once within `steps` steps; not every grid ever does
+pub fn synchronizes_within(grid: &Grid<u32>, steps: usize) -> bool {
+    let mut grid = grid.clone();
+    (0..steps).any(|_| step(&mut grid) == SIZE * SIZE)
+}
+
+/// The first step on which every octopus flashes, which never comes for some grids
+pub fn first_synchronized_step(mut grid: Grid<u32>) -> usize {
+    for i in 1.. {
+        runner::check_cancelled();
+        if step(&mut grid) == SIZE * SIZE {
+            return i;
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calculate1(s: &str) -> usize {
+        total_flashes(parse_octopuses(s).unwrap(), 100)
+    }
+
+    fn calculate2(s: &str) -> usize {
+        first_synchronized_step(parse_octopuses(s).unwrap())
+    }
+
+    const GIVEN_INPUT: &str = r#"5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526"#;
+
+    #[test]
+    fn test_new_grid_and_get() {
+        let grid = parse_octopuses(GIVEN_INPUT).unwrap();
+        assert_eq!(grid.iter().next(), Some(&5));
+        assert_eq!(grid.iter().last(), Some(&6));
+        assert_eq!(grid[(0, 0)], 5);
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid[(0, 1)], 2);
+        assert_eq!(grid[(1, 1)], 7);
+    }
+
+    #[test]
+    fn test_step() {
+        let mut grid = parse_octopuses(GIVEN_INPUT).unwrap();
+        let after = parse_octopuses(
+            r#"6594254334
+3856965822
+6375667284
+7252447257
+7468496589
+5278635756
+3287952832
+7993992245
+5957959665
+6394862637"#,
+        )
+        .unwrap();
+        step(&mut grid);
+        assert_eq!(grid, after)
+    }
+
+    #[test]
+    fn test_given_input() {
+        assert_eq!(calculate1(GIVEN_INPUT), 1656);
+    }
+
+    #[test]
+    fn test_given_input2() {
+        assert_eq!(calculate2(GIVEN_INPUT), 195);
+    }
+
+    #[test]
+    fn rejects_wrong_sizes() {
+        let err = parse_octopuses(&GIVEN_INPUT.replacen("2745854711", "274585471", 1)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 10));
+
+        let err =
+            parse_octopuses(&GIVEN_INPUT.replacen("2745854711", "27458547111", 1)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 11));
+
+        let err =
+            parse_octopuses(&GIVEN_INPUT.replacen("2745854711", "27458x4711", 1)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 6));
+
+        let err = parse_octopuses(&GIVEN_INPUT[..GIVEN_INPUT.len() - 11]).unwrap_err();
+        assert_eq!(err.found, "end of input");
+
+        assert!(parse_octopuses(&format!("{}\n1", GIVEN_INPUT)).is_err());
+    }
+}
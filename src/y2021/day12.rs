@@ -0,0 +1,234 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::parse::{self, ParseError, Span};
+use crate::runner;
+use crate::solution::Solution;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Graph;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Graph, ParseError> {
+        Graph::parse(input)
+    }
+
+    fn part1(input: &Graph) -> usize {
+        input.solution1()
+    }
+
+    fn part2(input: &Graph) -> usize {
+        input.solution2()
+    }
+}
+
+/// The cave system: which caves there are, and which are connected to each other
+pub struct Graph {
+    nodes: HashSet<String>,
+    edges: HashMap<String, HashSet<String>>,
+}
+
+impl Graph {
+    /// One connection per line, e.g. `start-A`
+    pub fn parse(s: impl AsRef<str>) -> Result<Self, ParseError> {
+        let s = s.as_ref();
+        let mut nodes = HashSet::new();
+        let mut edges = HashMap::new();
+        for line in Span::lines(s).filter(|s| !s.is_empty()) {
+            let (n1, n2) = parse::pair(line, "-", "a \"-\" and another cave")?;
+            let (n1, n2) = (parse_cave(n1)?, parse_cave(n2)?);
+
+            // two connected large caves could be bounced between forever
+            if is_large(n1) && is_large(n2) {
+                return Err(line.error("a connection involving a small cave"));
+            }
+
+            nodes.insert(n1.to_string());
+            nodes.insert(n2.to_string());
+
+            edges
+                .entry(n1.to_string())
+                .or_insert(HashSet::new())
+                .insert(n2.to_string());
+            edges
+                .entry(n2.to_string())
+                .or_insert(HashSet::new())
+                .insert(n1.to_string());
+        }
+
+        if !nodes.contains("start") {
+            return Err(ParseError::end_of_input(s, "a connection to \"start\""));
+        }
+
+        Ok(Self { nodes, edges })
+    }
+
+    pub fn caves(&self) -> impl Iterator<Item = &str> {
+        self.nodes.iter().map(String::as_str)
+    }
+
+    /// The caves directly connected to `cave`
+    pub fn connections<'a>(&'a self, cave: &str) -> impl Iterator<Item = &'a str> {
+        self.edges.get(cave).into_iter().flatten().map(String::as_str)
+    }
+
+    /// The number of paths from start to end that visit each small cave at most once
+    pub fn solution1(&self) -> usize {
+        self.list_paths_impl("start".into(), HashSet::from(["start".to_string()]), true)
+            .len()
+    }
+
+    /// The number of paths from start to end that visit at most one small cave twice
+    pub fn solution2(&self) -> usize {
+        self.list_paths().len()
+    }
+
+    /// The paths counted by [`Graph::solution2`], written like `start,A,b,end`
+    pub fn list_paths(&self) -> Vec<String> {
+        self.list_paths_impl("start".into(), HashSet::from(["start".to_string()]), false)
+    }
+
+    fn list_paths_impl(
+        &self,
+        start: String,
+        mut visited: HashSet<String>,
+        visited_twice: bool,
+    ) -> Vec<String> {
+        runner::check_cancelled();
+        visited.insert(start.clone());
+        if start == "end" {
+            return vec!["end".to_string()];
+        }
+        let mut result = vec![];
+        let candidates = self.edges.get(&start).unwrap().clone();
+
+        for node in candidates {
+            if node != "start" {
+
+            let paths = if is_large(&node) || !visited.contains(&node) {
+                self.list_paths_impl(node.to_string(), visited.clone(), visited_twice)
+            } else if !visited_twice && visited.contains(&node) {
+                self.list_paths_impl(node.to_string(), visited.clone(), true)
+            } else {
+                vec![]
+            };
+            result.extend(paths.into_iter().map(|s| format!("{},{}", start, s)));
+            }
+        }
+        result
+    }
+}
+
+/// A cave's name is either all uppercase (large) or all lowercase (small)
+fn parse_cave(s: Span<'_>) -> Result<&str, ParseError> {
+    let name = s.as_str();
+    let valid = name.chars().all(|c| c.is_ascii_uppercase())
+        || name.chars().all(|c| c.is_ascii_lowercase());
+    if name.is_empty() || !valid {
+        return Err(s.error("an all uppercase or all lowercase cave name"));
+    }
+    Ok(name)
+}
+
+fn is_large(s: &str) -> bool {
+    s.chars().next().unwrap().is_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIVEN_INPUT: &str = r#"fs-end
+he-DX
+fs-he
+start-DX
+pj-DX
+end-zg
+zg-sl
+zg-pj
+pj-he
+RW-he
+fs-DX
+pj-RW
+zg-RW
+start-pj
+he-WI
+zg-he
+pj-fs
+start-RW"#;
+
+    #[test]
+    fn given_example() {
+        let graph = Graph::parse(GIVEN_INPUT).unwrap();
+        assert_eq!(graph.solution1(), 226);
+        assert_eq!(graph.solution2(), 3509);
+    }
+
+    #[test]
+    fn test_is_large() {
+        assert!(!is_large("asdf"));
+        assert!(is_large("AS"));
+    }
+
+    #[test]
+    fn parse_grid() {
+        let grid = Graph::parse(
+            r#"start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end"#,
+        ).unwrap();
+
+        assert_eq!(
+            grid.edges.get("A").unwrap(),
+            &HashSet::from([
+                "start".to_string(),
+                "c".to_string(),
+                "b".to_string(),
+                "end".to_string(),
+            ])
+        );
+        assert_eq!(
+            grid.edges.get("c").unwrap(),
+            &HashSet::from(["A".to_string()])
+        );
+        assert_eq!(grid.caves().count(), 6);
+        assert_eq!(grid.connections("c").collect::<Vec<_>>(), ["A"]);
+        assert_eq!(grid.connections("x").count(), 0);
+        let graph = Graph::parse("start-A\nA-end\nb-end\nA-b").unwrap();
+        assert_eq!(
+            graph.edges.get("A").unwrap(),
+            &HashSet::from(["start".to_string(), "b".to_string(), "end".to_string()])
+        );
+
+        dbg!(grid.list_paths());
+        assert_eq!(grid.solution1(), 10);
+        assert_eq!(grid.solution2(), 36);
+    }
+
+    #[test]
+    fn trivial_examples() {
+        assert_eq!(Graph::parse("start-end").unwrap().solution2(), 1);
+        assert_eq!(Graph::parse("start-a\na-end").unwrap().solution2(), 1);
+        dbg!(Graph::parse("start-A\nA-end\nb-end\nA-b").unwrap().list_paths());
+        assert_eq!(Graph::parse("start-A\nA-end\nb-end\nA-b").unwrap().solution2(), 5);
+    }
+
+    #[test]
+    fn rejects_bad_connections() {
+        let err = Graph::parse("start-A\nA-bC").err().unwrap();
+        assert_eq!((err.line, err.column), (2, 3));
+
+        let err = Graph::parse("start-A\nA").err().unwrap();
+        assert_eq!((err.column, err.found.as_str()), (2, "end of line"));
+
+        assert!(Graph::parse("start-A\nA-B").is_err());
+        assert!(Graph::parse("a-end").is_err());
+        assert!(Graph::parse("start-a-end").is_err());
+    }
+}
@@ -0,0 +1,212 @@
+use crate::grid::Grid;
+use crate::parse::{self, ParseError, Span};
+use crate::solution::Solution;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = (Paper, Vec<Fold>);
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<(Paper, Vec<Fold>), ParseError> {
+        parse_input(input)
+    }
+
+    fn part1((paper, folds): &(Paper, Vec<Fold>)) -> usize {
+        dots(&apply_fold(paper, &folds[0]))
+    }
+
+    fn part2((paper, folds): &(Paper, Vec<Fold>)) -> String {
+        let paper = folds
+            .iter()
+            .fold(paper.clone(), |paper, fold| apply_fold(&paper, fold));
+        render(&paper)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fold {
+    Horizontal(usize),
+    Vertical(usize),
+}
+
+/// Which positions on the transparent paper have dots
+pub type Paper = Grid<bool>;
+
+/// Dots and folds must be closer to the corner than this, so the paper fits in memory
+const MAX_COORDINATE: usize = 10_000;
+
+/// The dots, one `x,y` per line, then a blank line and the fold instructions
+pub fn parse_input(s: impl AsRef<str>) -> Result<(Paper, Vec<Fold>), ParseError> {
+    let s = s.as_ref();
+    let mut sections = Span::sections(s);
+    let mut points = vec![];
+    for line in sections.next().unwrap_or_default() {
+        let (x, y) = parse::pair(line, ",", "a comma")?;
+        points.push((coordinate(x)?, coordinate(y)?));
+    }
+    let folds = sections
+        .flatten()
+        .map(parse_fold)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if folds.is_empty() {
+        return Err(ParseError::end_of_input(s, "a fold instruction"));
+    }
+
+    let width = points.iter().map(|(x, _)| x).max().unwrap_or(&0) + 1;
+    let height = points.iter().map(|(_, y)| y).max().unwrap_or(&0) + 1;
+    let mut paper = Grid::new(width, height, false);
+    for point in points {
+        paper[point] = true;
+    }
+    Ok((paper, folds))
+}
+
+/// Fold the bottom (or right) half over onto the top (or left), dropping the fold line itself
+pub fn apply_fold(paper: &Paper, fold: &Fold) -> Paper {
+    let dot = |x, y| paper.get(x, y).copied().unwrap_or(false);
+    match *fold {
+        Fold::Horizontal(n) => {
+            Grid::from_fn(paper.width(), n, |x, y| dot(x, y) || dot(x, 2 * n - y))
+        }
+        Fold::Vertical(n) => {
+            Grid::from_fn(n, paper.height(), |x, y| dot(x, y) || dot(2 * n - x, y))
+        }
+    }
+}
+
+/// How many dots can be seen
+pub fn dots(paper: &Paper) -> usize {
+    paper.iter().filter(|dot| **dot).count()
+}
+
+/// The paper drawn with `#` for dots and `.` for blanks, one line per row
+pub fn render(paper: &Paper) -> String {
+    paper.render(|&dot| if dot { '#' } else { '.' })
+}
+
+/// A fold instruction like `fold along x=5`
+fn parse_fold(line: Span) -> Result<Fold, ParseError> {
+    let expected = "a fold like \"fold along x=5\"";
+    if !line.as_str().starts_with("fold along ") {
+        return Err(line.error(expected));
+    }
+
+    let (_, instruction) = line.split_once("fold along ").unwrap();
+    let (xy, num) = instruction
+        .split_once("=")
+        .ok_or_else(|| instruction.error(expected))?;
+    let num = coordinate(num)?;
+    match xy.as_str() {
+        "x" => Ok(Fold::Vertical(num)),
+        "y" => Ok(Fold::Horizontal(num)),
+        _ => Err(xy.error("\"x\" or \"y\"")),
+    }
+}
+
+fn coordinate(s: Span) -> Result<usize, ParseError> {
+    match s.parse("a number")? {
+        n if n < MAX_COORDINATE => Ok(n),
+        _ => Err(s.error(format!("a number below {}", MAX_COORDINATE))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const GIVEN_INPUT: &str = r#"6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5"#;
+
+    #[test]
+    fn parse_paper() {
+        let (paper, folds) = parse_input(GIVEN_INPUT).unwrap();
+        assert_eq!(dots(&paper), 18);
+        assert!(paper[(6, 10)]);
+        assert!(paper[(0, 14)]);
+        assert_eq!(folds, vec![Fold::Horizontal(7), Fold::Vertical(5)]);
+        assert_eq!(paper.height(), 15);
+        assert_eq!(paper.width(), 11);
+    }
+
+    #[test]
+    fn given_example() {
+        let (paper, folds) = parse_input(GIVEN_INPUT).unwrap();
+        let paper = apply_fold(&paper, &folds[0]);
+        assert_eq!(dots(&paper), 17);
+        let paper = apply_fold(&paper, &folds[1]);
+        assert_eq!(dots(&paper), 16);
+
+        assert_eq!(
+            Day13::part2(&parse_input(GIVEN_INPUT).unwrap()),
+            "#####\n#...#\n#...#\n#...#\n#####\n.....\n.....\n"
+        );
+    }
+
+    #[test]
+    fn rejects_bad_instructions() {
+        let err = parse_input("6,10\n0,x\n\nfold along y=7").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+
+        let err = parse_input("6,10\n\nfold along z=7").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 12));
+
+        let err = parse_input("6,10\n\nfold y=7").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+
+        let err = parse_input("6,10\n").unwrap_err();
+        assert_eq!(err.found, "end of input");
+
+        // far too big a paper to hold
+        let err = parse_input("6,10\n99999999,2\n\nfold along y=7").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        let err = parse_input("6,10\n\nfold along x=18446744073709551615").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 14));
+    }
+
+    /// A paper of up to 20 by 20, and a fold along one of its rows or columns
+    fn folds() -> impl Strategy<Value = (Paper, Fold)> {
+        (1..=20usize, 1..=20usize).prop_flat_map(|(width, height)| {
+            let paper = proptest::collection::vec(any::<bool>(), width * height)
+                .prop_map(move |dots| Grid::from_fn(width, height, |x, y| dots[y * width + x]));
+            let fold = prop_oneof![
+                (1..=height).prop_map(Fold::Horizontal),
+                (1..=width).prop_map(Fold::Vertical),
+            ];
+            (paper, fold)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn folding_never_adds_dots((paper, fold) in folds()) {
+            let folded = apply_fold(&paper, &fold);
+            prop_assert!(dots(&folded) <= dots(&paper));
+            // everything is on one side of the fold now, so doing it again changes nothing
+            prop_assert_eq!(apply_fold(&folded, &fold), folded);
+        }
+    }
+}
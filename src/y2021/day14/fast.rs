@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use super::Question;
+use crate::parse::ParseError;
 
 struct State {
     map: HashMap<(char, char), char>,
@@ -47,10 +48,14 @@ fn merge(mut m1: HashMap<char, usize>, m2: HashMap<char, usize>) -> HashMap<char
     m1
 }
 
-pub fn calculate(s: impl AsRef<str>, depth: usize) -> usize {
-    let Question { pairs, template } = Question::new(s);
+pub fn calculate(s: impl AsRef<str>, depth: usize) -> Result<usize, ParseError> {
+    Ok(most_minus_least_common(&Question::new(s)?, depth))
+}
+
+pub fn most_minus_least_common(question: &Question, depth: usize) -> usize {
+    let Question { pairs, template } = question;
     let mut state = State {
-        map: pairs,
+        map: pairs.clone(),
         cache: HashMap::new(),
     };
 
@@ -98,14 +103,14 @@ CN -> C"#;
 
     #[test]
     fn trivial() {
-        assert_eq!(calculate(GIVEN_INPUT, 10), 1588);
+        assert_eq!(calculate(GIVEN_INPUT, 10), Ok(1588));
     }
 
     #[test]
     #[ignore]
     fn fast_method_given_input() {
-        dbg!(calculate(GIVEN_INPUT, 39));
-        dbg!(calculate(GIVEN_INPUT, 41));
-        assert_eq!(calculate(GIVEN_INPUT, 40), 2188189693529);
+        dbg!(calculate(GIVEN_INPUT, 39).unwrap());
+        dbg!(calculate(GIVEN_INPUT, 41).unwrap());
+        assert_eq!(calculate(GIVEN_INPUT, 40), Ok(2188189693529));
     }
 }
@@ -0,0 +1,209 @@
+pub mod fast;
+
+
+use std::collections::{HashMap, HashSet};
+
+use crate::parse::{self, ParseError, Span};
+use crate::solution::Solution;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Question;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Question, ParseError> {
+        Question::new(input)
+    }
+
+    fn part1(input: &Question) -> usize {
+        most_minus_least_common(input.clone(), 10)
+    }
+
+    fn part2(input: &Question) -> usize {
+        fast::most_minus_least_common(input, 40)
+    }
+}
+
+/// A polymer template and the pair insertion rules
+#[derive(Debug, Clone)]
+pub struct Question {
+    template: String,
+    pairs: HashMap<(char, char), char>,
+}
+
+/// Apply `steps` steps by building the whole polymer, then subtract the least common element's
+/// count from the most common one's
+pub fn most_minus_least_common(mut q: Question, steps: usize) -> usize {
+    for _ in 0..steps {
+        q.step();
+    }
+
+    let all_chars: HashSet<_> = q.template.chars().collect();
+    let char_counts: HashMap<_, _> = all_chars
+        .iter()
+        .map(|c| (*c, q.template.chars().filter(|c1| c1 == c).count()))
+        .collect();
+
+    let max_char = *char_counts.values().max().unwrap();
+    let min_char = *char_counts.values().min().unwrap();
+
+    max_char - min_char
+}
+
+impl Question {
+    /// A template of at least two elements, a blank line, then a rule like `AB -> C` for every
+    /// pair of elements that appears
+    pub fn new(s: impl AsRef<str>) -> Result<Self, ParseError> {
+        let s = s.as_ref();
+        let mut sections = Span::sections(s);
+        let template = sections
+            .next()
+            .ok_or_else(|| ParseError::end_of_input(s, "a polymer template"))?;
+        if let Some(line) = template.get(1) {
+            return Err(line.error("a blank line"));
+        }
+        let template = template[0];
+        if template.as_str().len() < 2 {
+            return Err(template.error("a template of at least two elements"));
+        }
+        let template = parse_elements(template, usize::MAX)?.into_iter().collect();
+
+        let mut pairs = HashMap::new();
+        for line in sections.flatten() {
+            let (pair, insert) = parse::rule(line, " -> ", "a rule like \"AB -> C\"")?;
+            let pair = parse_elements(pair, 2)?;
+            let insert = parse_elements(insert, 1)?;
+            pairs.insert((pair[0], pair[1]), insert[0]);
+        }
+
+        let question = Self { template, pairs };
+        let elements: HashSet<_> = question
+            .pairs
+            .iter()
+            .flat_map(|((a, b), c)| [*a, *b, *c])
+            .chain(question.template.chars())
+            .collect();
+        for a in &elements {
+            for b in &elements {
+                if !question.pairs.contains_key(&(*a, *b)) {
+                    let rule = format!("a rule for \"{}{}\"", a, b);
+                    return Err(ParseError::end_of_input(s, rule));
+                }
+            }
+        }
+
+        Ok(question)
+    }
+
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// Insert an element between every pair in the template
+    pub fn step(&mut self) {
+        let bytes = self.template.as_bytes();
+        let insertions = bytes
+            .windows(2)
+            .map(|c| self.pairs.get(&(c[0] as char, c[1] as char)).unwrap());
+        let first = bytes[0];
+
+        let mut mapped_bytes: Vec<u8> = bytes
+            .iter()
+            .skip(1)
+            .zip(insertions)
+            .flat_map(|(a, b)| [*b as u8, *a])
+            .collect();
+
+        mapped_bytes.insert(0, first);
+
+        self.template = String::from_utf8(mapped_bytes).unwrap();
+    }
+}
+
+/// Exactly `count` elements (uppercase letters), or any number if `count` is `usize::MAX`
+fn parse_elements(s: Span, count: usize) -> Result<Vec<char>, ParseError> {
+    let mut elements = vec![];
+    for (c, element) in s.chars() {
+        if !element.is_ascii_uppercase() {
+            return Err(c.error("an element from A to Z"));
+        }
+        if elements.len() == count {
+            return Err(c.error("end of the rule"));
+        }
+        elements.push(element);
+    }
+    if count != usize::MAX && elements.len() < count {
+        return Err(s.end_error("an element from A to Z"));
+    }
+    Ok(elements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calculate1(s: impl AsRef<str>) -> usize {
+        most_minus_least_common(Question::new(s).unwrap(), 10)
+    }
+
+    const GIVEN_INPUT: &str = r#"NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C"#;
+
+    #[test]
+    fn test_given_input() {
+        let mut q = Question::new(GIVEN_INPUT).unwrap();
+        assert_eq!(q.template, "NNCB");
+        assert_eq!(q.pairs.get(&('C', 'H')).unwrap(), &'B');
+        assert_eq!(q.pairs.get(&('C', 'N')).unwrap(), &'C');
+
+        q.step();
+        assert_eq!(q.template, "NCNBCHB");
+        q.step();
+        assert_eq!(q.template, "NBCCNBBBCBHCB");
+        q.step();
+        assert_eq!(q.template, "NBBBCNCCNBBNBNBBCHBHHBCHB");
+        q.step();
+        assert_eq!(
+            q.template,
+            "NBBNBNBBCCNBCNCCNBBNBBNBBBNBBNBBCBHCBHHNHCBBCBHCB"
+        );
+
+        assert_eq!(calculate1(GIVEN_INPUT), 1588);
+    }
+
+    #[test]
+    fn rejects_bad_rules() {
+        let err = Question::new(GIVEN_INPUT.replace("HH -> N", "Hh -> N")).unwrap_err();
+        assert_eq!((err.line, err.column), (4, 2));
+
+        let err = Question::new(GIVEN_INPUT.replace("HH -> N", "HH-> N")).unwrap_err();
+        assert_eq!((err.line, err.column), (4, 1));
+
+        let err = Question::new(GIVEN_INPUT.replace("HH -> N", "HHH -> N")).unwrap_err();
+        assert_eq!((err.line, err.column), (4, 3));
+
+        let err = Question::new(GIVEN_INPUT.replace("\nHH -> N", "")).unwrap_err();
+        assert_eq!(err.expected, "a rule for \"HH\"");
+
+        assert!(Question::new("N\n\nNN -> N").is_err());
+        assert!(Question::new("NN\nNN -> N").is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use pathfinding::prelude::dijkstra;
+
+use crate::grid::Grid;
+use crate::parse::{ParseError, Span};
+use crate::solution::Solution;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Grid<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Grid<u64>, ParseError> {
+        parse_risk_levels(input)
+    }
+
+    fn part1(input: &Grid<u64>) -> u64 {
+        lowest_total_risk(input)
+    }
+
+    fn part2(input: &Grid<u64>) -> u64 {
+        lowest_total_risk(&expand(input))
+    }
+}
+
+fn wrap(i: u64) -> u64 {
+    if i > 9 {
+        i - 9
+    } else {
+        i
+    }
+}
+
+/// The full cave: the grid repeated 5 times in each direction, adding 1 to the risk levels
+/// (wrapping 9 back round to 1) for each step right or down
+pub fn expand(grid: &Grid<u64>) -> Grid<u64> {
+    let (width, height) = (grid.width(), grid.height());
+    Grid::from_fn(width * 5, height * 5, |x, y| {
+        let shift = (x / width + y / height) as u64;
+        wrap(grid.get_wrapping(x as isize, y as isize) + shift)
+    })
+}
+
+/// A square grid of risk levels from 1 to 9
+pub fn parse_risk_levels(s: &str) -> Result<Grid<u64>, ParseError> {
+    let grid = Grid::parse_digits(s, "risk level", 1..=9)?;
+    let size = grid.width();
+    if grid.height() < size {
+        return Err(ParseError::end_of_input(
+            s,
+            format!("{} rows of risk levels, to make a square", size),
+        ));
+    }
+    if let Some(extra) = Span::lines(s).filter(|line| !line.is_empty()).nth(size) {
+        return Err(extra.error(format!("{} rows of risk levels, to make a square", size)));
+    }
+    Ok(grid)
+}
+
+/// The path from the top left to the bottom right with the lowest total risk, and that risk. The
+/// starting position's risk isn't counted, since you're already there.
+pub fn safest_path(grid: &Grid<u64>) -> (Vec<(usize, usize)>, u64) {
+    let end = (grid.width() - 1, grid.height() - 1);
+    dijkstra(
+        &(0usize, 0usize),
+        |&(x, y)| grid.neighbors4(x, y).map(|n| (n, grid[n])),
+        |coord| coord == &end,
+    )
+    .unwrap()
+}
+
+pub fn lowest_total_risk(grid: &Grid<u64>) -> u64 {
+    safest_path(grid).1
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input_const;
+
+    use super::*;
+
+    const GIVEN_INPUT: &str = r#"1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581"#;
+
+    #[test]
+    fn given_input() {
+        let grid = parse_risk_levels(GIVEN_INPUT).unwrap();
+        assert_eq!(grid.width(), 10);
+        assert_eq!(grid[(0, 0)], 1);
+        assert_eq!(grid[(1, 1)], 3);
+        assert_eq!(grid[(9, 9)], 1);
+        assert_eq!(lowest_total_risk(&grid), 40);
+
+        let (path, _) = safest_path(&grid);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(9, 9)));
+        assert_eq!(path.len(), 19);
+    }
+
+    #[test]
+    fn parse_real_input() {
+        let grid = parse_risk_levels(input_const!(2021, 15)).unwrap();
+        assert_eq!(grid.width(), 100);
+        assert_eq!(grid.iter().count(), 10000);
+        assert_eq!(grid[(0, 0)], 2);
+        assert_eq!(grid[(1, 1)], 1);
+        assert_eq!(grid[(99, 99)], 9);
+    }
+
+    #[test]
+    fn part_2_given() {
+        let grid = parse_risk_levels(GIVEN_INPUT).unwrap();
+        let grid = expand(&grid);
+        assert_eq!(lowest_total_risk(&grid), 315);
+    }
+
+    #[test]
+    fn rejects_bad_grids() {
+        let err = parse_risk_levels("116\n130\n213").err().unwrap();
+        assert_eq!((err.line, err.column), (2, 3));
+
+        let err = parse_risk_levels("116\n13\n213").err().unwrap();
+        assert_eq!((err.line, err.expected.as_str()), (2, "a row of 3 risk levels"));
+
+        assert!(parse_risk_levels("116\n138").is_err());
+        assert!(parse_risk_levels("").is_err());
+    }
+}
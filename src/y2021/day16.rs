@@ -0,0 +1,505 @@
+use std::fmt::Write;
+
+use crate::parse::{self, ParseError, Span};
+use crate::solution::Solution;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Packet;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Packet, ParseError> {
+        parse_hex_packet(input)
+    }
+
+    fn part1(input: &Packet) -> u64 {
+        input.version_sum()
+    }
+
+    fn part2(input: &Packet) -> u64 {
+        input.eval()
+    }
+}
+
+/// A decoded BITS packet, which is either a literal value or an operator on its sub-packets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packet {
+    version: u8,
+    type_id: u8,
+    kind: PacketKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PacketKind {
+    /// The value's bits, most significant first
+    Literal(String),
+    Sum(Vec<Packet>),
+    Product(Vec<Packet>),
+    Min(Vec<Packet>),
+    Max(Vec<Packet>),
+    Lt(Vec<Packet>),
+    Gt(Vec<Packet>),
+    Eq(Vec<Packet>),
+}
+
+impl Packet {
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn type_id(&self) -> u8 {
+        self.type_id
+    }
+
+    pub fn kind(&self) -> &PacketKind {
+        &self.kind
+    }
+
+    /// The versions of this packet and every packet inside it, added up
+    pub fn version_sum(&self) -> u64 {
+        let mut result = self.version as u64;
+        for packet in self.sub_packets() {
+            result += packet.version_sum();
+        }
+        result
+    }
+
+    /// The packets directly inside this one, which is none for a literal
+    pub fn sub_packets(&self) -> &[Packet] {
+        use PacketKind::*;
+        match &self.kind {
+            Literal(_) => &[],
+            Sum(packets) | Product(packets) | Min(packets) | Max(packets) | Lt(packets)
+            | Gt(packets) | Eq(packets) => packets,
+        }
+    }
+
+    /// The value of the expression this packet encodes. Sums and products wrap around if they
+    /// don't fit in 64 bits, which never happens with real transmissions.
+    pub fn eval(&self) -> u64 {
+        use PacketKind::*;
+        match &self.kind {
+            Literal(s) => u64::from_str_radix(s, 2).unwrap(),
+            Sum(packets) => packets.iter().map(Self::eval).fold(0, u64::wrapping_add),
+            Product(packets) => packets.iter().map(Self::eval).fold(1, u64::wrapping_mul),
+            Min(packets) => packets.iter().map(Self::eval).min().unwrap(),
+            Max(packets) => packets.iter().map(Self::eval).max().unwrap(),
+            Lt(packets) => u64::from(packets[0].eval() < packets[1].eval()),
+            Gt(packets) => u64::from(packets[0].eval() > packets[1].eval()),
+            Eq(packets) => u64::from(packets[0].eval() == packets[1].eval()),
+        }
+    }
+
+    /// The packet as a hexadecimal transmission, which [`parse_hex_packet`] decodes back into
+    /// this packet. Sub-packets are introduced by their length in bits when that fits in the 15
+    /// bits for it, and by their number otherwise.
+    pub fn encode(&self) -> String {
+        let mut bits = String::new();
+        self.write_bits(&mut bits);
+        while !bits.len().is_multiple_of(4) {
+            bits.push('0');
+        }
+        bits.as_bytes()
+            .chunks(4)
+            .map(|digit| {
+                let digit = digit.iter().fold(0, |n, bit| n * 2 + (bit - b'0') as u32);
+                std::char::from_digit(digit, 16)
+                    .unwrap()
+                    .to_ascii_uppercase()
+            })
+            .collect()
+    }
+
+    fn write_bits(&self, bits: &mut String) {
+        write!(bits, "{:03b}{:03b}", self.version, self.type_id).unwrap();
+        match &self.kind {
+            PacketKind::Literal(value) => {
+                let groups = value.len() / 4;
+                for (i, group) in value.as_bytes().chunks(4).enumerate() {
+                    bits.push(if i + 1 < groups { '1' } else { '0' });
+                    bits.extend(group.iter().map(|&bit| bit as char));
+                }
+            }
+            _ => {
+                let mut sub_packets = String::new();
+                for packet in self.sub_packets() {
+                    packet.write_bits(&mut sub_packets);
+                }
+                if sub_packets.len() < 1 << 15 {
+                    write!(bits, "0{:015b}", sub_packets.len()).unwrap();
+                } else {
+                    write!(bits, "1{:011b}", self.sub_packets().len()).unwrap();
+                }
+                bits.push_str(&sub_packets);
+            }
+        }
+    }
+}
+
+/// Decode the outermost packet of the only non-blank line, a transmission in hexadecimal
+pub fn parse_hex_packet(s: &str) -> Result<Packet, ParseError> {
+    let line = parse::single_line(s, "a hexadecimal transmission")?;
+    let bits = hex_to_binary(line)?;
+
+    parse_packet(&bits, 0)
+        .map(|(packet, _)| packet)
+        .map_err(|e| {
+            // point at the hex digit containing the offending bit
+            let bit = bits.len() - e.remaining;
+            let digit = bit / 4;
+            ParseError::new(
+                line.line(),
+                line.column() + digit,
+                e.expected,
+                match line.as_str().get(digit..digit + 1) {
+                    Some(c) => format!("bit {} of {:?}", bit % 4, c),
+                    None => "end of transmission".to_string(),
+                },
+            )
+        })
+}
+
+/// Something wrong inside the binary transmission
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BitError {
+    /// The number of bits left when the error was found, which locates it
+    remaining: usize,
+    expected: &'static str,
+}
+
+fn hex_to_binary(s: Span) -> Result<String, ParseError> {
+    let single = |(span, c): (Span, char)| match c {
+        '0' => Ok("0000"),
+        '1' => Ok("0001"),
+        '2' => Ok("0010"),
+        '3' => Ok("0011"),
+        '4' => Ok("0100"),
+        '5' => Ok("0101"),
+        '6' => Ok("0110"),
+        '7' => Ok("0111"),
+        '8' => Ok("1000"),
+        '9' => Ok("1001"),
+        'A' => Ok("1010"),
+        'B' => Ok("1011"),
+        'C' => Ok("1100"),
+        'D' => Ok("1101"),
+        'E' => Ok("1110"),
+        'F' => Ok("1111"),
+        _ => Err(span.error("a hexadecimal digit")),
+    };
+
+    s.chars().map(single).collect()
+}
+
+/// Split off the first `n` bits, or fail if there aren't that many left
+fn take<'a>(s: &'a str, n: usize, expected: &'static str) -> Result<(&'a str, &'a str), BitError> {
+    if s.len() < n {
+        Err(BitError {
+            remaining: s.len(),
+            expected,
+        })
+    } else {
+        Ok(s.split_at(n))
+    }
+}
+
+/// Packets can't be nested deeper than this, so that a crafted transmission can't overflow the
+/// stack. Real ones are nested a few dozen deep at most.
+const MAX_DEPTH: usize = 200;
+
+/// A packet nested inside `depth` operators
+fn parse_packet(s: &str, depth: usize) -> Result<(Packet, &str), BitError> {
+    if depth > MAX_DEPTH {
+        return Err(BitError {
+            remaining: s.len(),
+            expected: "packets nested at most 200 deep",
+        });
+    }
+    let (s, version) = parse_version(s)?;
+    let (s, type_id) = parse_type_id(s)?;
+
+    let (kind, s) = if type_id == 4 {
+        parse_literal(s)?
+    } else {
+        parse_operator(type_id, s, depth)?
+    };
+    Ok((
+        Packet {
+            version,
+            type_id,
+            kind,
+        },
+        s,
+    ))
+}
+
+fn parse_version(s: &str) -> Result<(&str, u8), BitError> {
+    let (version, rest) = take(s, 3, "a 3 bit packet version")?;
+    Ok((rest, u8::from_str_radix(version, 2).unwrap()))
+}
+
+fn parse_type_id(s: &str) -> Result<(&str, u8), BitError> {
+    let (type_id, rest) = take(s, 3, "a 3 bit type ID")?;
+    Ok((rest, u8::from_str_radix(type_id, 2).unwrap()))
+}
+
+fn parse_literal(s: &str) -> Result<(PacketKind, &str), BitError> {
+    let start = s;
+    let mut s = s;
+    let mut result = String::new();
+    loop {
+        let (rest, chunk, more_chunks) = parse_literal_chunk(s)?;
+        result.push_str(chunk);
+        s = rest;
+
+        if result.len() > 64 {
+            return Err(BitError {
+                remaining: start.len(),
+                expected: "a literal that fits in 64 bits",
+            });
+        }
+
+        if !more_chunks {
+            return Ok((PacketKind::Literal(result), rest));
+        }
+    }
+}
+
+fn parse_literal_chunk(s: &str) -> Result<(&str, &str, bool), BitError> {
+    let (chunk, rest) = take(s, 5, "a 5 bit group of a literal")?;
+    Ok((rest, &chunk[1..], chunk.starts_with('1')))
+}
+
+fn parse_operator(type_id: u8, s: &str, depth: usize) -> Result<(PacketKind, &str), BitError> {
+    let start = s;
+    let (length_type_id, s) = take(s, 1, "a length type ID")?;
+    let length_type_id = length_type_id == "1";
+    let mut packets = vec![];
+    let remaining = if length_type_id {
+        let (num_packets, mut s) = take(s, 11, "an 11 bit number of sub-packets")?;
+        let num_packets = usize::from_str_radix(num_packets, 2).unwrap();
+        for _ in 0..num_packets {
+            let (packet, remaining) = parse_packet(s, depth + 1)?;
+            packets.push(packet);
+            s = remaining;
+        }
+
+        s
+    } else {
+        let (num_bits, s) = take(s, 15, "a 15 bit length of sub-packets")?;
+        let num_bits = usize::from_str_radix(num_bits, 2).unwrap();
+        let (mut packet_string, s) = take(s, num_bits, "as many bits as the sub-packet length")?;
+        while !packet_string.is_empty() {
+            let (packet, remaining) =
+                parse_packet(packet_string, depth + 1).map_err(|e| BitError {
+                    // errors inside the sub-packets are located relative to the whole operator
+                    remaining: e.remaining + s.len(),
+                    ..e
+                })?;
+            packets.push(packet);
+            packet_string = remaining;
+        }
+        s
+    };
+
+    let expected_count = match type_id {
+        5..=7 => 2..=2,
+        _ => 1..=usize::MAX,
+    };
+    if !expected_count.contains(&packets.len()) {
+        return Err(BitError {
+            remaining: start.len(),
+            expected: if type_id >= 5 {
+                "exactly 2 sub-packets"
+            } else {
+                "at least 1 sub-packet"
+            },
+        });
+    }
+
+    let kind = match type_id {
+        0 => PacketKind::Sum(packets),
+        1 => PacketKind::Product(packets),
+        2 => PacketKind::Min(packets),
+        3 => PacketKind::Max(packets),
+        5 => PacketKind::Gt(packets),
+        6 => PacketKind::Lt(packets),
+        7 => PacketKind::Eq(packets),
+        _ => unreachable!(),
+    };
+
+    Ok((kind, remaining))
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn calculate(s: &str) -> u64 {
+        parse_hex_packet(s).unwrap().version_sum()
+    }
+
+    #[test]
+    fn check_hex_to_bin() {
+        let span = Span::lines("D2FE28").next().unwrap();
+        assert_eq!(hex_to_binary(span).unwrap(), "110100101111111000101000");
+    }
+
+    #[test]
+    fn full_example() {
+        let s = "110100101111111000101000";
+        let (s, version) = parse_version(s).unwrap();
+        assert_eq!((s, version), ("100101111111000101000", 6));
+        let (s, type_id) = parse_type_id(s).unwrap();
+        assert_eq!((s, type_id), ("101111111000101000", 4));
+        let (kind, _) = parse_literal(s).unwrap();
+        assert_eq!(kind, PacketKind::Literal("011111100101".to_string()));
+    }
+
+    #[test]
+    fn given_examples() {
+        assert_eq!(
+            parse_hex_packet("D2FE28").unwrap(),
+            Packet {
+                version: 6,
+                type_id: 4,
+                kind: PacketKind::Literal("011111100101".into()),
+            }
+        );
+
+        let packet = parse_hex_packet("38006F45291200").unwrap();
+        assert_eq!(packet.version, 1);
+        assert_eq!(packet.type_id, 6);
+
+        assert_eq!(calculate("8A004A801A8002F478"), 16);
+        assert_eq!(calculate("A0016C880162017C3686B18A3D4780"), 31);
+
+        assert_eq!(
+            parse_hex_packet("9C0141080250320F1802104A08")
+                .unwrap()
+                .eval(),
+            1
+        );
+    }
+
+    #[test]
+    fn reports_where_the_transmission_is_broken() {
+        let err = parse_hex_packet("D2FG28").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 4));
+
+        // the literal's last group is cut off
+        let err = parse_hex_packet("D2FE2").unwrap_err();
+        assert_eq!(
+            (err.column, err.expected.as_str()),
+            (5, "a 5 bit group of a literal")
+        );
+        assert_eq!(err.found, "bit 0 of \"2\"");
+
+        // a less-than operator with a single sub-packet
+        let err = parse_hex_packet("1A004408").unwrap_err();
+        assert_eq!(
+            (err.column, err.expected.as_str()),
+            (2, "exactly 2 sub-packets")
+        );
+
+        assert!(parse_hex_packet("\n").is_err());
+        assert!(parse_hex_packet("38006F45291200").is_ok());
+
+        let nested = |depth| {
+            let literal = Packet {
+                version: 0,
+                type_id: 4,
+                kind: PacketKind::Literal("0001".to_string()),
+            };
+            (0..depth).fold(literal, |inner, _| Packet {
+                version: 0,
+                type_id: 0,
+                kind: PacketKind::Sum(vec![inner]),
+            })
+        };
+        assert_eq!(
+            parse_hex_packet(&nested(MAX_DEPTH).encode())
+                .unwrap()
+                .eval(),
+            1
+        );
+        let err = parse_hex_packet(&nested(MAX_DEPTH + 1).encode()).unwrap_err();
+        assert_eq!(err.expected, "packets nested at most 200 deep");
+    }
+
+    #[test]
+    fn sums_and_products_wrap() {
+        let literal = |value: u64| Packet {
+            version: 0,
+            type_id: 4,
+            kind: PacketKind::Literal(format!("{:064b}", value)),
+        };
+        let operator = |type_id, kind: fn(Vec<Packet>) -> PacketKind| Packet {
+            version: 0,
+            type_id,
+            kind: kind(vec![literal(u64::MAX), literal(3)]),
+        };
+        assert_eq!(operator(0, PacketKind::Sum).eval(), 2);
+        assert_eq!(operator(1, PacketKind::Product).eval(), u64::MAX - 2);
+    }
+
+    /// Well-formed packets, nested up to 4 deep
+    fn packets() -> impl Strategy<Value = Packet> {
+        let literal =
+            (0..8u8, proptest::collection::vec(0..16u8, 1..=16)).prop_map(|(version, groups)| {
+                Packet {
+                    version,
+                    type_id: 4,
+                    kind: PacketKind::Literal(
+                        groups.iter().map(|g| format!("{:04b}", g)).collect(),
+                    ),
+                }
+            });
+        literal.prop_recursive(4, 64, 4, |inner| {
+            let operator = prop_oneof![0..4u8, 5..8u8];
+            let sub_packets = proptest::collection::vec(inner, 1..=4);
+            (0..8u8, operator, sub_packets).prop_map(|(version, type_id, mut packets)| {
+                use PacketKind::*;
+                let kind = match type_id {
+                    0 => Sum(packets),
+                    1 => Product(packets),
+                    2 => Min(packets),
+                    3 => Max(packets),
+                    comparison => {
+                        packets.resize(2, packets[0].clone());
+                        match comparison {
+                            5 => Gt(packets),
+                            6 => Lt(packets),
+                            _ => Eq(packets),
+                        }
+                    }
+                };
+                Packet {
+                    version,
+                    type_id,
+                    kind,
+                }
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn encoding_round_trips(packet in packets()) {
+            let hex = packet.encode();
+            prop_assert_eq!(parse_hex_packet(&hex), Ok(packet));
+        }
+    }
+
+    #[test]
+    fn encodes_examples() {
+        assert_eq!(parse_hex_packet("D2FE28").unwrap().encode(), "D2FE28");
+        // sub-packets by length rather than by number, but otherwise the same
+        let packet = parse_hex_packet("EE00D40C823060").unwrap();
+        assert_eq!(parse_hex_packet(&packet.encode()), Ok(packet));
+    }
+}
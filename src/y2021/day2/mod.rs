@@ -0,0 +1,177 @@
+use crate::parse::{self, ParseError, Span};
+use crate::solution::Solution;
+use Instruction::*;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Instruction>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+        parse_instructions(input)
+    }
+
+    fn part1(input: &Vec<Instruction>) -> i64 {
+        // without aim, "up" and "down" change the depth directly, which is exactly how aim moves
+        let mut p = Position::default();
+        apply_all(&mut p, input.iter().copied());
+        p.aim * p.horizontal
+    }
+
+    fn part2(input: &Vec<Instruction>) -> i64 {
+        let mut p = Position::default();
+        apply_all(&mut p, input.iter().copied());
+        p.depth * p.horizontal
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+pub enum Instruction {
+    Forward(i64),
+    Up(i64),
+    Down(i64),
+}
+
+/// Where the submarine is, and where it's aiming
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Default)]
+pub struct Position {
+    pub depth: i64,
+    pub horizontal: i64,
+    pub aim: i64,
+}
+
+pub fn apply_instruction(p: &mut Position, instruction: Instruction) {
+    match instruction {
+        Forward(i) => {
+            p.horizontal += i;
+            p.depth += i * p.aim;
+        }
+        Up(i) => p.aim -= i,
+        Down(i) => p.aim += i,
+    }
+}
+
+pub fn apply_all(p: &mut Position, i: impl IntoIterator<Item = Instruction>) {
+    for instruction in i {
+        apply_instruction(p, instruction)
+    }
+}
+
+/// One instruction like `forward 5` per line
+pub fn parse_instructions(s: &str) -> Result<Vec<Instruction>, ParseError> {
+    Span::lines(s).map(map_line).collect()
+}
+
+fn map_line(line: Span) -> Result<Instruction, ParseError> {
+    let mut parts = line.split_whitespace();
+    let instruction = parse::next(&mut parts, line, "an instruction")?;
+    let i: i64 = parse::next(&mut parts, line, "a number")?.parse("a number")?;
+    parse::end(&mut parts)?;
+    match instruction.as_str() {
+        "forward" => Ok(Forward(i)),
+        "up" => Ok(Up(i)),
+        "down" => Ok(Down(i)),
+        _ => Err(instruction.error(r#""forward", "up" or "down""#)),
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use crate::input_const;
+
+    use super::*;
+
+    fn test_input() -> Vec<&'static str> {
+        vec![
+            "forward 5",
+            "down 5",
+            "forward 8",
+            "up 3",
+            "down 8",
+            "forward 2",
+        ]
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let input = parse_instructions(input_const!(2021, 2)).unwrap();
+        assert_eq!(input.first(), Some(&Forward(2)));
+        assert_eq!(input.last(), Some(&Forward(6)));
+    }
+
+    #[test]
+    fn test_given_input() {
+        let mut p = Position::default();
+        apply_all(&mut p, parse_instructions(&test_input().join("\n")).unwrap());
+        assert_eq!(p.horizontal, 15);
+        assert_eq!(p.depth, 60);
+    }
+
+    #[test]
+    fn test_given_input_parts() {
+        let input = Day2::parse(&test_input().join("\n")).unwrap();
+        assert_eq!(Day2::part1(&input), 150);
+        assert_eq!(Day2::part2(&input), 900);
+    }
+
+    #[test]
+    fn test_map_line() {
+        let parsed = parse_instructions(&test_input().join("\n")).unwrap();
+        assert_eq!(
+            parsed,
+            vec![Forward(5), Down(5), Forward(8), Up(3), Down(8), Forward(2),]
+        );
+    }
+
+    #[test]
+    fn test_map_line_errors() {
+        let err = parse_instructions("forward 5\nsideways 3").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, r#""forward", "up" or "down""#);
+
+        let err = parse_instructions("down").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (1, 5, "end of line"));
+
+        let err = parse_instructions("up 3 4").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 6));
+    }
+
+    #[test]
+    fn test_apply_instruction() {
+        let mut pos = Position::default();
+
+        apply_instruction(&mut pos, Forward(1));
+        assert_eq!(pos, Position {
+            depth: 0,
+            horizontal: 1,
+            aim: 0,
+        });
+        
+        apply_instruction(&mut pos, Up(1));
+        assert_eq!(pos, Position {
+            depth: 0,
+            horizontal: 1,
+            aim: -1,
+        });
+        
+        apply_instruction(&mut pos, Forward(10));
+        assert_eq!(pos, Position {
+            depth: -10,
+            horizontal: 11,
+            aim: -1,
+        });
+        
+        apply_instruction(&mut pos, Down(10));
+        assert_eq!(pos, Position {
+            depth: -10,
+            horizontal: 11,
+            aim: 9,
+        });
+    }
+
+}
@@ -0,0 +1,117 @@
+use crate::parse::{ParseError, Span};
+
+pub fn parse_input(s: &str) -> Result<(usize, Vec<u16>), ParseError> {
+    let numbers = super::parse_input(s)?;
+    let length = numbers[0].len();
+    if length > 16 {
+        let first = Span::lines(s).next().unwrap();
+        return Err(first.error("a binary number of at most 16 digits"));
+    }
+    let numbers = numbers
+        .iter()
+        .map(|s| u16::from_str_radix(s, 2).unwrap())
+        .collect();
+    Ok((length, numbers))
+}
+
+pub fn solution1(input: &str) -> Result<u64, ParseError> {
+    let (length, nums) = parse_input(input)?;
+    Ok(power_consumption(length, &nums))
+}
+
+pub fn power_consumption(length: usize, nums: &[u16]) -> u64 {
+    let (gamma, epsilon) = gamma_and_epsilon(length, nums.iter().copied());
+    gamma as u64 * epsilon as u64
+}
+
+fn gamma_and_epsilon(length: usize, nums: impl IntoIterator<Item = u16>) -> (u16, u16) {
+    let mcbs = most_common_bits(length, nums);
+    let lcbs = invert(mcbs, length);
+    (mcbs, lcbs)
+}
+
+fn most_common_bits(length: usize, nums: impl IntoIterator<Item = u16>) -> u16 {
+    let mut ones = vec![0usize; length];
+    let nums: Vec<_> = nums.into_iter().collect();
+    let nums_len = nums.len();
+
+    for num in nums {
+        for (n, count) in ones.iter_mut().enumerate() {
+            if nth_bit(num, n) {
+                *count += 1;
+            }
+        }
+    }
+
+    let mut zeroes = vec![0usize; length];
+    for (index, count) in ones.iter().enumerate() {
+        zeroes[index] = nums_len - count;
+    }
+
+    let mut output = 0u16;
+    for i in 0..length {
+        let more_zeroes = zeroes[i] > ones[i];
+        if !more_zeroes {
+            output |= 1 << i;
+        }
+    }
+    output
+}
+
+fn nth_bit(i: u16, n: usize) -> bool {
+    i & (1 << n) != 0
+}
+
+/// Flip the lowest `length` bits, leaving the unused high bits clear
+fn invert(i: u16, length: usize) -> u16 {
+    let mask = u16::MAX >> (16 - length);
+    !i & mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invert() {
+        assert_eq!(invert(0b0101010101010101, 16), 0b1010101010101010);
+        assert_eq!(invert(0b10110, 5), 0b01001);
+    }
+
+    fn test_input() -> &'static str {
+        r#"00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010"#
+    }
+
+    #[test]
+    fn test_given_input() {
+        let input = test_input()
+            .lines()
+            .map(|s| u16::from_str_radix(s, 2).unwrap());
+        let mcbs = most_common_bits(5, input);
+        assert_eq!(mcbs, 0b10110);
+        assert_eq!(solution1(test_input()), Ok(198));
+    }
+
+    #[test]
+    fn test_nth_bit() {
+        assert!(nth_bit(0b1111, 0));
+        assert!(nth_bit(0b1111, 1));
+        assert!(nth_bit(0b1111, 2));
+        assert!(nth_bit(0b1111, 3));
+        assert!(!nth_bit(0b1111, 4));
+        assert!(!nth_bit(0b1111, 5));
+        assert!(!nth_bit(0b1111, 6));
+        assert!(!nth_bit(0b1111, 7));
+    }
+}
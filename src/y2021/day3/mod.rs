@@ -0,0 +1,230 @@
+
+// (hopefully) faster implementation
+pub mod fast;
+
+use crate::parse::{ParseError, Span};
+use crate::solution::Solution;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<String>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<String>) -> i64 {
+        let length = input[0].len();
+        let (gamma, epsilon) = gamma_and_epsilon(length, input);
+
+        let gamma = i64::from_str_radix(&gamma, 2).unwrap();
+        let epsilon = i64::from_str_radix(&epsilon, 2).unwrap();
+
+        gamma * epsilon
+    }
+
+    fn part2(input: &Vec<String>) -> i64 {
+        let (oxy, co2) = ratings(input);
+
+        let oxy = i64::from_str_radix(&oxy, 2).unwrap();
+        let co2 = i64::from_str_radix(&co2, 2).unwrap();
+
+        oxy * co2
+    }
+}
+
+/// The diagnostic report: binary numbers that all have the same number of digits
+pub fn parse_input(s: &str) -> Result<Vec<String>, ParseError> {
+    let mut numbers: Vec<String> = vec![];
+
+    for line in Span::lines(s) {
+        if let Some((c, _)) = line.chars().find(|(_, c)| *c != '0' && *c != '1') {
+            return Err(c.error("a binary digit"));
+        }
+
+        let expected_len = numbers.first().map(String::len);
+        match expected_len {
+            None if line.is_empty() || line.as_str().len() > 63 => {
+                return Err(line.error("a binary number of 1 to 63 digits"))
+            }
+            Some(len) if line.as_str().len() != len => {
+                return Err(line.error(format!("a binary number of {} digits", len)))
+            }
+            _ => numbers.push(line.as_str().to_owned()),
+        }
+    }
+
+    if numbers.is_empty() {
+        Err(ParseError::end_of_input(s, "a binary number"))
+    } else {
+        Ok(numbers)
+    }
+}
+
+/// The gamma rate (the most common bit in each position) and the epsilon rate (the least common)
+pub fn gamma_and_epsilon(
+    length: usize,
+    nums: impl IntoIterator<Item = impl AsRef<str>>,
+) -> (String, String) {
+    let mcbs = most_common_bits(length, nums);
+    let lcbs = invert(&mcbs);
+    (mcbs, lcbs)
+}
+
+fn most_common_bits(length: usize, nums: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+    let mut zeroes = vec![0usize; length];
+    let mut ones = vec![0usize; length];
+
+    for num in nums {
+        for (i, chr) in num.as_ref().chars().enumerate() {
+            match chr {
+                '0' => zeroes[i] += 1,
+                '1' => ones[i] += 1,
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    let mut s = String::with_capacity(length);
+    for i in 0..length {
+        let more_zeroes = zeroes[i] > ones[i];
+        s.push(if more_zeroes { '0' } else { '1' });
+    }
+    s
+}
+
+fn invert(s: impl AsRef<str>) -> String {
+    let s = s.as_ref();
+    let s = s.replace("0", "t");
+    let s = s.replace("1", "0");
+    s.replace("t", "1")
+}
+
+/// The oxygen generator and CO2 scrubber ratings
+pub fn ratings(input: impl IntoIterator<Item = impl AsRef<str> + Clone>) -> (String, String) {
+    let mut oxy_readings: Vec<_> = input.into_iter().collect();
+    let mut co2_readings = oxy_readings.clone();
+
+    let length = oxy_readings[0].as_ref().len();
+
+    let mut current_index = 0;
+    let nth = |s: &str, n: usize| s.chars().collect::<Vec<_>>()[n];
+
+    while oxy_readings.len() != 1 || co2_readings.len() != 1 {
+        if oxy_readings.len() > 1 {
+            let mcbs = most_common_bits(length, &oxy_readings);
+            let bit = nth(&mcbs, current_index);
+            oxy_readings.retain(|s| nth(s.as_ref(), current_index) == bit);
+        }
+
+        if co2_readings.len() > 1 {
+            let lcbs = invert(most_common_bits(length, &co2_readings));
+            let bit = nth(&lcbs, current_index);
+            // if every reading has the same bit here, none has the least common one, so this bit
+            // can't narrow them down
+            if co2_readings.iter().any(|s| nth(s.as_ref(), current_index) == bit) {
+                co2_readings.retain(|s| nth(s.as_ref(), current_index) == bit);
+            }
+        }
+
+        current_index += 1;
+    }
+
+    (
+        oxy_readings[0].as_ref().to_owned(),
+        co2_readings[0].as_ref().to_owned(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    fn test_input() -> &'static str {
+        r#"00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010"#
+    }
+
+    #[test]
+    fn test_invert() {
+        assert_eq!(invert(""), "");
+        assert_eq!(invert("1"), "0");
+        assert_eq!(invert("0"), "1");
+        assert_eq!(invert("101010"), "010101");
+        assert_eq!(invert("110110110"), "001001001");
+    }
+
+    #[test]
+    fn given_input() {
+        let mcbs = most_common_bits(5, test_input().lines());
+        assert_eq!(mcbs, "10110");
+    }
+
+    #[test]
+    fn test_ratings() {
+        let (oxy, co2) = ratings(test_input().lines());
+
+        assert_eq!(oxy, "10111");
+        assert_eq!(co2, "01010");
+
+        // every reading starts with 1, so the first bit says nothing about co2
+        let (_, co2) = ratings(["110", "101", "111"]);
+        assert_eq!(co2, "101");
+    }
+
+    #[test]
+    fn given_input_parts() {
+        let input = Day3::parse(test_input()).unwrap();
+        assert_eq!(Day3::part1(&input), 198);
+        assert_eq!(Day3::part2(&input), 230);
+    }
+
+    #[test]
+    fn rejects_malformed_report() {
+        let err = parse_input("0101\n0121").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+
+        let err = parse_input("0101\n011").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "a binary number of 4 digits");
+
+        assert!(parse_input("").is_err());
+    }
+
+    /// `length` and some binary numbers of that length
+    fn reports() -> impl Strategy<Value = (usize, Vec<String>)> {
+        (1..=63usize).prop_flat_map(|length| {
+            let number = proptest::collection::vec(any::<bool>(), length)
+                .prop_map(|bits| bits.iter().map(|&bit| if bit { '1' } else { '0' }).collect());
+            (Just(length), proptest::collection::vec(number, 1..50))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn gamma_and_epsilon_are_complements((length, numbers) in reports()) {
+            prop_assert_eq!(&parse_input(&numbers.join("\n")).unwrap(), &numbers);
+
+            let (gamma, epsilon) = gamma_and_epsilon(length, &numbers);
+            let gamma = u64::from_str_radix(&gamma, 2).unwrap();
+            let epsilon = u64::from_str_radix(&epsilon, 2).unwrap();
+            prop_assert_eq!(gamma & epsilon, 0);
+            prop_assert_eq!(gamma | epsilon, (1 << length) - 1);
+        }
+    }
+}
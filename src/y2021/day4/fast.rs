@@ -1,6 +1,9 @@
+use crate::parse::{self, ParseError, Span};
+
 const WIDTH: usize = 5;
 const ELEMS: usize = WIDTH * WIDTH;
 
+#[derive(Clone)]
 struct Board([Option<i32>; ELEMS]);
 
 impl Board {
@@ -45,33 +48,43 @@ impl Board {
     } 
 }
 
-struct Game {
+#[derive(Clone)]
+pub struct Game {
     inputs: Vec<i32>,
     boards: Vec<Board>,
 }
 
 impl Game {
-    fn from_str(s: impl AsRef<str>) -> Self {
-        let mut lines = s.as_ref().lines();
-        let input_line = lines.next().unwrap();
-        let inputs = input_line.split(",").map(|s| s.parse().unwrap()).collect();
-
-        lines.next().unwrap();
-
-        let remaining = lines.map(ToOwned::to_owned).collect::<Vec<_>>();
-        let board_strings = remaining.split(String::is_empty);
+    pub fn parse(s: impl AsRef<str>) -> Result<Self, ParseError> {
+        let s = s.as_ref();
+        let mut sections = Span::sections(s);
+        let first = sections
+            .next()
+            .ok_or_else(|| ParseError::end_of_input(s, "the numbers to draw"))?;
+        if let Some(line) = first.get(1) {
+            return Err(line.error("a blank line"));
+        }
+        let inputs = parse::numbers(first[0], ",", "a number to draw")?;
 
         let mut boards = vec![];
 
-        for board in board_strings {
+        for board in sections {
             let values = board
                 .iter()
                 .flat_map(|s| s.split_whitespace())
-                .map(|s| s.parse().unwrap());
+                .map(|s| s.parse("a number"))
+                .collect::<Result<Vec<_>, _>>()?;
+            if values.len() != ELEMS {
+                return Err(board[0].error(format!("a board of {} numbers", ELEMS)));
+            }
             boards.push(Board::new(values));
         }
 
-        Game { boards, inputs }
+        if boards.is_empty() {
+            return Err(ParseError::end_of_input(s, "a board"));
+        }
+
+        Ok(Game { boards, inputs })
     }
 
     fn step(&mut self) -> i32 {
@@ -86,9 +99,11 @@ impl Game {
 
 }
 
-pub fn calculate1(s: impl AsRef<str>) -> i32 {
-    let mut game = Game::from_str(s.as_ref());
+pub fn calculate1(s: impl AsRef<str>) -> Result<i32, ParseError> {
+    Ok(first_winner_score(Game::parse(s.as_ref())?))
+}
 
+pub fn first_winner_score(mut game: Game) -> i32 {
     let (board, last_input) = loop {
         let input = game.step();
         if let Some(board) = game.single_winner() {
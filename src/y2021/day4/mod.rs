@@ -1,11 +1,33 @@
-mod fast;
+pub mod fast;
 
 use std::collections::HashMap;
 
-use crate::input_lines;
+use crate::parse::{self, ParseError, Span};
+use crate::runner;
+use crate::solution::Solution;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Game;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Game, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Game) -> i64 {
+        first_winner_score(input.clone())
+    }
+
+    fn part2(input: &Game) -> i64 {
+        last_winner_score(input.clone())
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Game {
+pub struct Game {
     inputs: Vec<i32>,
     boards: Vec<Board>,
 }
@@ -15,17 +37,10 @@ struct Board {
     map: HashMap<(usize, usize), Option<i32>>,
 }
 
-fn input() -> impl IntoIterator<Item = &'static str> {
-    input_lines!("4")
-}
-
-pub fn solution2() -> i64 {
-    let game = parse_input(input());
-    calculate_2(game)
-}
-
-fn calculate_2(mut game: Game) -> i64 {
+/// The score of the board that wins last: its unmarked numbers times the number that won it
+pub fn last_winner_score(mut game: Game) -> i64 {
     let (last_input, loser_index) = 'outer: loop {
+        runner::check_cancelled();
         let mut input = step_game(&mut game);
 
         let mut loser_indices = vec![];
@@ -54,12 +69,8 @@ fn calculate_2(mut game: Game) -> i64 {
     remaining_numbers as i64 * last_input as i64
 }
 
-pub fn solution1() -> i64 {
-    let game = parse_input(input());
-    calculate(game)
-}
-
-fn calculate(mut game: Game) -> i64 {
+/// The score of the board that wins first: its unmarked numbers times the number that won it
+pub fn first_winner_score(mut game: Game) -> i64 {
     let (last_input, winner_index) = 'outer: loop {
         let input = step_game(&mut game);
 
@@ -79,30 +90,55 @@ fn calculate(mut game: Game) -> i64 {
     remaining_numbers as i64 * last_input as i64
 }
 
-fn parse_input(lines: impl IntoIterator<Item = &'static str>) -> Game {
-    let mut lines = lines.into_iter();
-    let inputs = lines.next().unwrap();
-    let inputs: Vec<i32> = inputs.split(",").map(|s| s.parse().unwrap()).collect();
+/// The numbers to draw on the first line, then 5x5 boards separated by blank lines
+pub fn parse_input(s: &str) -> Result<Game, ParseError> {
+    let mut sections = Span::sections(s);
+    let first = sections
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(s, "the numbers to draw"))?;
+    if let Some(line) = first.get(1) {
+        return Err(line.error("a blank line"));
+    }
+    let inputs = parse::numbers(first[0], ",", "a number to draw")?;
 
-    lines.next().unwrap();
-    let lines: Vec<_> = lines.map(ToOwned::to_owned).collect();
+    let boards = sections
+        .map(|board| parse_board(&board))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    let splits = lines.split(String::is_empty);
+    if boards.is_empty() {
+        return Err(ParseError::end_of_input(s, "a board"));
+    }
 
-    let mut boards = vec![];
+    Ok(Game { inputs, boards })
+}
 
-    for split in splits {
-        let mut map = HashMap::with_capacity(25);
-        for (i, line) in split.iter().enumerate() {
-            for (j, s) in line.split_whitespace().enumerate() {
-                map.insert((i, j), Some(s.parse().unwrap()));
-            }
+/// A board is 5 lines of 5 whitespace-separated numbers
+fn parse_board(lines: &[Span]) -> Result<Board, ParseError> {
+    if lines.len() > 5 {
+        return Err(lines[5].error("a blank line between boards"));
+    }
+
+    let mut map = HashMap::with_capacity(25);
+    for (i, line) in lines.iter().enumerate() {
+        let mut numbers = line.split_whitespace();
+        for j in 0..5 {
+            let number = parse::next(&mut numbers, *line, "5 numbers on each row")?;
+            map.insert((i, j), Some(number.parse("a number")?));
         }
+        parse::end(&mut numbers)?;
+    }
 
-        boards.push(Board { map });
+    if lines.len() < 5 {
+        let last = lines[lines.len() - 1];
+        return Err(ParseError::new(
+            last.line() + 1,
+            1,
+            "5 rows in each board",
+            "a blank line",
+        ));
     }
 
-    Game { inputs, boards }
+    Ok(Board { map })
 }
 
 /// Draw the next number, remove it from the board and return the drawn number
@@ -110,7 +146,7 @@ fn step_game(game: &mut Game) -> i32 {
     let input = game.inputs.remove(0);
 
     for board in game.boards.iter_mut() {
-        for (_, value) in board.map.iter_mut() {
+        for value in board.map.values_mut() {
             if Some(input) == *value {
                 *value = None;
             }
@@ -129,10 +165,6 @@ fn check_win(board: &Board) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use test::{black_box, Bencher};
-
-    use crate::input_const;
-
     use super::*;
 
     const GIVEN_INPUT: &str = r#"7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
@@ -157,7 +189,7 @@ mod tests {
 
     #[test]
     fn correctly_parses_inputs() {
-        let game = parse_input(GIVEN_INPUT.lines());
+        let game = parse_input(GIVEN_INPUT).unwrap();
 
         assert_eq!(game.inputs.first(), Some(&7));
         assert_eq!(game.inputs.last(), Some(&1));
@@ -174,7 +206,7 @@ mod tests {
 
     #[test]
     fn steps_game() {
-        let mut game = parse_input(GIVEN_INPUT.lines());
+        let mut game = parse_input(GIVEN_INPUT).unwrap();
 
         let input = step_game(&mut game);
 
@@ -185,7 +217,7 @@ mod tests {
 
     #[test]
     fn check_win_test() {
-        let mut game = parse_input(GIVEN_INPUT.lines());
+        let mut game = parse_input(GIVEN_INPUT).unwrap();
 
         assert!(!check_win(&game.boards[0]));
 
@@ -198,27 +230,37 @@ mod tests {
 
     #[test]
     fn check_given_input() {
-        let game = parse_input(GIVEN_INPUT.lines());
-        let answer = calculate(game);
+        let game = parse_input(GIVEN_INPUT).unwrap();
+        let answer = first_winner_score(game);
         assert_eq!(answer, 4512);
     }
 
     #[test]
     fn check_given_input2() {
-        let game = parse_input(GIVEN_INPUT.lines());
-        let answer = calculate_2(game);
+        let game = parse_input(GIVEN_INPUT).unwrap();
+        let answer = last_winner_score(game);
         assert_eq!(answer, 1924);
     }
 
-    #[bench]
-    fn slow_calculate(b: &mut Bencher) {
-        let input = input_const!("4");
-        b.iter(|| black_box(calculate(parse_input(black_box(input.lines())))))
-    }
-
-    #[bench]
-    fn fast_calcuate(b: &mut Bencher) {
-        let input = input_const!("4");
-        b.iter(|| black_box(fast::calculate1(black_box(input))))
+    #[test]
+    fn rejects_malformed_boards() {
+        let err = parse_input("1,2,x\n\n1 2 3 4 5").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 5));
+
+        let short_row = GIVEN_INPUT.replacen(" 8  2 23  4 24", " 8  2 23  4", 1);
+        let err = parse_input(&short_row).unwrap_err();
+        assert_eq!((err.line, err.column), (4, 12));
+        assert_eq!(err.found, "end of line");
+
+        let short_board = GIVEN_INPUT.replacen(" 1 12 20 15 19\n", "", 1);
+        let err = parse_input(&short_board).unwrap_err();
+        assert_eq!(
+            (err.line, err.expected.as_str()),
+            (7, "5 rows in each board")
+        );
+
+        assert!(parse_input("1,2,3\n").is_err());
+        assert!(fast::calculate1(&short_row).is_err());
+        assert!(fast::calculate1(&short_board).is_err());
     }
 }
@@ -0,0 +1,363 @@
+use crate::parse::{self, ParseError, Span};
+use crate::solution::Solution;
+
+use model::{Line, Point};
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<Line>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Line>, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Vec<Line>) -> usize {
+        let straight: Vec<_> = input
+            .iter()
+            .filter(|line| !line.is_diagonal())
+            .copied()
+            .collect();
+        count_overlaps(&straight)
+    }
+
+    fn part2(input: &Vec<Line>) -> usize {
+        count_overlaps(input)
+    }
+}
+
+pub mod model {
+    use std::cmp::{max, min};
+
+    use rayon::iter::IntoParallelIterator;
+    use rayon::iter::ParallelIterator;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use crate::grid::Grid;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Point {
+        pub x: usize,
+        pub y: usize,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Line {
+        pub start: Point,
+        pub end: Point,
+    }
+
+    impl Line {
+        pub fn is_diagonal(&self) -> bool {
+            self.start.x != self.end.x && self.start.y != self.end.y
+        }
+
+        pub fn all_points(&self) -> Vec<Point> {
+            let Point { x: x1, y: y1 } = self.start;
+            let Point { x: x2, y: y2 } = self.end;
+
+            if x1 == x2 {
+                let (y1, y2) = (min(y1, y2), max(y1, y2));
+                let x = x1;
+                (y1..=y2).map(|y| Point { x, y }).collect()
+            } else if y1 == y2 {
+                let (x1, x2) = (min(x1, x2), max(x1, x2));
+                let y = y1;
+                (x1..=x2).map(|x| Point { x, y }).collect()
+            } else {
+                let (x_asc, y_asc) = (x2 > x1, y2 > y1);
+                let mut result = vec![Point { x: x1, y: y1 }];
+                let (mut x, mut y) = (x1, y1);
+                let count = if y_asc { y2 - y1 } else { y1 - y2 };
+
+                for _ in 0..count {
+                    if x_asc {
+                        x += 1;
+                    } else {
+                        x -= 1;
+                    }
+                    if y_asc {
+                        y += 1;
+                    } else {
+                        y -= 1;
+                    }
+                    result.push(Point { x, y });
+                }
+
+                result
+            }
+        }
+    }
+
+    /// How many lines cover each point
+    pub fn new_counts(width: usize, height: usize) -> Grid<AtomicUsize> {
+        Grid::from_fn(width, height, |_, _| AtomicUsize::new(0))
+    }
+
+    pub fn apply_lines(counts: &Grid<AtomicUsize>, lines: impl IntoParallelIterator<Item = Line>) {
+        lines
+            .into_par_iter()
+            .flat_map(|line| line.all_points())
+            .for_each(|Point { x, y }| {
+                counts[(x, y)].fetch_add(1, Ordering::Relaxed);
+            });
+    }
+
+    pub fn count_greater_than_one(counts: &Grid<AtomicUsize>) -> usize {
+        counts
+            .iter()
+            .filter(|i| i.load(Ordering::Relaxed) >= 2)
+            .count()
+    }
+}
+
+/// How many points at least two lines pass through
+pub fn count_overlaps(lines: &[Line]) -> usize {
+    let (width, height) = max_dimensions(lines);
+    let counts = model::new_counts(width, height);
+    model::apply_lines(&counts, lines.to_vec());
+    model::count_greater_than_one(&counts)
+}
+
+/// One line like `0,9 -> 5,9` per line of input
+pub fn parse_lines(s: impl AsRef<str>) -> Result<Vec<Line>, ParseError> {
+    Span::lines(s.as_ref()).map(parse_line).collect()
+}
+
+/// The width and height of a grid big enough to hold every line
+pub fn max_dimensions(lines: &[Line]) -> (usize, usize) {
+    let points = lines.iter().flat_map(|line| [line.start, line.end]);
+    let max_width = points.clone().map(|p| p.x).max().unwrap_or(0);
+    let max_height = points.map(|p| p.y).max().unwrap_or(0);
+
+    (max_width + 1, max_height + 1)
+}
+
+fn parse_line(s: Span) -> Result<Line, ParseError> {
+    let (start, end) = parse::rule(s, " -> ", "a line like \"x1,y1 -> x2,y2\"")?;
+
+    let start = parse_point(start)?;
+    let end = parse_point(end)?;
+    let line = Line { start, end };
+
+    // diagonal lines are only walked one step at a time in both directions
+    let (dx, dy) = (start.x.abs_diff(end.x), start.y.abs_diff(end.y));
+    if line.is_diagonal() && dx != dy {
+        return Err(s.error("a horizontal, vertical or 45 degree line"));
+    }
+
+    Ok(line)
+}
+
+/// Points must be closer to the corner than this, so the grid of counts fits in memory. Real
+/// inputs stay below 1000.
+const MAX_COORDINATE: usize = 2_000;
+
+#[inline(always)]
+fn parse_point(s: Span) -> Result<Point, ParseError> {
+    let (x, y) = parse::pair(s, ",", "a comma")?;
+    Ok(Point {
+        x: coordinate(x)?,
+        y: coordinate(y)?,
+    })
+}
+
+fn coordinate(s: Span) -> Result<usize, ParseError> {
+    match s.parse("a number")? {
+        n if n < MAX_COORDINATE => Ok(n),
+        _ => Err(s.error(format!("a number below {}", MAX_COORDINATE))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::atomic::Ordering;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn span(s: &str) -> Span<'_> {
+        Span::lines(s).next().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let line = parse_line(span("3,4 -> 5,6")).unwrap();
+        assert_eq!(
+            line,
+            Line {
+                start: Point { x: 3, y: 4 },
+                end: Point { x: 5, y: 6 },
+            }
+        )
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_line(span("3,4 -> 5,x")).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (10, "a number"));
+
+        let err = parse_line(span("3,4 5,6")).unwrap_err();
+        assert_eq!(err.column, 1);
+
+        let err = parse_line(span("3,4 -> 5")).unwrap_err();
+        assert_eq!((err.column, err.found.as_str()), (9, "end of line"));
+
+        assert!(parse_line(span("0,0 -> 2,1")).is_err());
+        assert_eq!(parse_lines("0,0 -> 1,1\n0,0 ->").unwrap_err().line, 2);
+
+        // a grid this big wouldn't fit in memory
+        let err = parse_line(span("99999999999,0 -> 99999999999,1")).unwrap_err();
+        assert_eq!(
+            (err.column, err.expected.as_str()),
+            (1, "a number below 2000")
+        );
+        let err = parse_line(span("0,0 -> 0,18446744073709551615")).unwrap_err();
+        assert_eq!(err.column, 10);
+    }
+
+    #[test]
+    fn grid_new() {
+        let grid = model::new_counts(5, 5);
+        for x in 0..5 {
+            for y in 0..5 {
+                assert_eq!(grid[(x, y)].load(Ordering::Relaxed), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_line_and_count() {
+        let grid = model::new_counts(5, 5);
+        let lines = (0..5).map(|x| Line {
+            start: Point { x, y: 0 },
+            end: Point { x, y: 4 },
+        });
+
+        model::apply_lines(&grid, lines.collect::<Vec<_>>());
+
+        for x in 0..5 {
+            for y in 0..5 {
+                assert_eq!(grid[(x, y)].load(Ordering::Relaxed), 1);
+            }
+        }
+
+        let second_line = Line {
+            start: Point { x: 0, y: 0 },
+            end: Point { x: 0, y: 4 },
+        };
+
+        model::apply_lines(&grid, vec![second_line; 100]);
+
+        for y in 0..5 {
+            assert_eq!(grid[(0, y)].load(Ordering::Relaxed), 101)
+        }
+    }
+
+    #[test]
+    fn test_points_from_line() {
+        let bl = Point { x: 0, y: 0 };
+        let br = Point { x: 2, y: 0 };
+        let tl = Point { x: 0, y: 2 };
+        let tr = Point { x: 2, y: 2 };
+
+        assert_eq!(
+            Line { start: br, end: bl }.all_points(),
+            vec![bl, Point { x: 1, y: 0 }, br,]
+        );
+
+        assert_eq!(
+            Line { start: bl, end: tl }.all_points(),
+            vec![bl, Point { x: 0, y: 1 }, tl,]
+        );
+
+        assert_eq!(
+            Line { start: br, end: tl }.all_points(),
+            vec![br, Point { x: 1, y: 1 }, tl,]
+        );
+
+        assert_eq!(
+            Line { start: tl, end: br }.all_points(),
+            vec![tl, Point { x: 1, y: 1 }, br,]
+        );
+
+        assert_eq!(
+            Line { start: tr, end: bl }.all_points(),
+            vec![tr, Point { x: 1, y: 1 }, bl,]
+        );
+
+        assert_eq!(Line { start: br, end: br }.all_points(), vec![br]);
+    }
+
+    #[test]
+    fn check_given_input() {
+        let input = r#"0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2"#;
+
+        let lines = parse_lines(input).unwrap();
+        assert_eq!(max_dimensions(&lines), (10, 10));
+        assert_eq!(lines.len(), 10);
+        assert_eq!(count_overlaps(&lines), 12);
+
+        let lines = Day5::parse(input).unwrap();
+        assert_eq!(Day5::part1(&lines), 5);
+        assert_eq!(Day5::part2(&lines), 12);
+    }
+
+    /// Horizontal, vertical and diagonal lines, which are the only ones that parse
+    fn lines() -> impl Strategy<Value = Line> {
+        // far enough from 0 to go 100 steps in any direction
+        let point = (100..200usize, 100..200usize).prop_map(|(x, y)| Point { x, y });
+        let direction = (0..3u8, any::<bool>(), any::<bool>());
+        (point, direction, 0..100usize).prop_map(|(start, (direction, right, up), length)| {
+            let along = |n: usize, forward| if forward { n + length } else { n - length };
+            let end = match direction {
+                0 => Point {
+                    x: along(start.x, right),
+                    ..start
+                },
+                1 => Point {
+                    y: along(start.y, up),
+                    ..start
+                },
+                _ => Point {
+                    x: along(start.x, right),
+                    y: along(start.y, up),
+                },
+            };
+            Line { start, end }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn all_points_goes_from_end_to_end(line in lines()) {
+            let Line { start, end } = line;
+            let text = format!("{},{} -> {},{}", start.x, start.y, end.x, end.y);
+            prop_assert_eq!(parse_line(span(&text)), Ok(line));
+
+            let points = line.all_points();
+            let (dx, dy) = (start.x.abs_diff(end.x), start.y.abs_diff(end.y));
+            prop_assert_eq!(points.len(), dx.max(dy) + 1);
+            let ends = [points[0], points[points.len() - 1]];
+            prop_assert!(ends == [start, end] || ends == [end, start]);
+            for pair in points.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                prop_assert_eq!(a.x.abs_diff(b.x).max(a.y.abs_diff(b.y)), 1);
+            }
+        }
+    }
+}
@@ -1,28 +1,28 @@
 use num_bigint::BigUint;
 
+use crate::parse::ParseError;
+
 const AGE_LIMIT: usize = 9;
 
-#[derive(Debug)]
-struct FishList {
+#[derive(Debug, Clone)]
+pub struct FishList {
     ages: [BigUint; AGE_LIMIT],
 }
 
 impl FishList {
-    fn from_str(s: impl AsRef<str>) -> Self {
-        let fishes: Vec<BigUint> = s
-            .as_ref()
-            .split(",")
-            .map(str::trim)
-            .map(|s| s.parse().unwrap())
-            .collect();
+    pub fn parse(s: impl AsRef<str>) -> Result<Self, ParseError> {
+        Ok(Self::from_timers(super::parse_timers(s.as_ref())?))
+    }
+
+    pub fn from_timers(timers: impl IntoIterator<Item = u8>) -> Self {
+        let fishes: Vec<BigUint> = timers.into_iter().map(BigUint::from).collect();
         let mut ages: [BigUint; AGE_LIMIT] = Default::default();
-        for i in 0..AGE_LIMIT {
-            ages[i] = fishes
+        for (i, age) in ages.iter_mut().enumerate() {
+            *age = fishes
                 .iter()
                 .filter(|n| **n == BigUint::from(i))
                 .count()
-                .try_into()
-                .unwrap()
+                .into()
         }
         Self { ages }
     }
@@ -41,8 +41,11 @@ impl FishList {
     }
 }
 
-pub fn calculate(s: impl AsRef<str>, days: usize) -> BigUint {
-    let mut fishlist = FishList::from_str(s);
+pub fn calculate(s: impl AsRef<str>, days: usize) -> Result<BigUint, ParseError> {
+    Ok(simulate(FishList::parse(s)?, days))
+}
+
+pub fn simulate(mut fishlist: FishList, days: usize) -> BigUint {
     for _ in 0..days {
         fishlist.step();
     }
@@ -56,7 +59,7 @@ mod tests {
 
     #[test]
     fn test_from_str() {
-        let mut list = FishList::from_str("3,4,3,1,2");
+        let mut list = FishList::parse("3,4,3,1,2").unwrap();
         assert_eq!(as_vec(&list), vec![0, 1, 1, 2, 1, 0, 0, 0, 0]);
         list.step();
         assert_eq!(as_vec(&list), vec![1, 1, 2, 1, 0, 0, 0, 0, 0]);
@@ -72,6 +75,9 @@ mod tests {
 
     #[test]
     fn test_given_example() {
-        assert_eq!(calculate("3,4,3,1,2", 256), BigUint::from(26984457539u64));
+        assert_eq!(
+            calculate("3,4,3,1,2", 256),
+            Ok(BigUint::from(26984457539u64))
+        );
     }
 }
@@ -0,0 +1,151 @@
+pub mod fast;
+
+use num_bigint::BigUint;
+
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = FishList;
+    type Answer1 = usize;
+    type Answer2 = BigUint;
+
+    fn parse(input: &str) -> Result<FishList, ParseError> {
+        FishList::parse(input)
+    }
+
+    fn part1(input: &FishList) -> usize {
+        simulate(input.clone(), 80)
+    }
+
+    fn part2(input: &FishList) -> BigUint {
+        let fishlist = fast::FishList::from_timers(input.0.iter().map(|fish| fish.0));
+        fast::simulate(fishlist, 256)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+struct Fish(u8);
+
+impl Fish {
+    fn step(&mut self) -> Option<Fish> {
+        match self.0 {
+            0 => {
+                self.0 = 6;
+                Some(Fish(8))
+            }
+            _ => {
+                self.0 -= 1;
+                None
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct FishList(Vec<Fish>);
+
+impl FishList {
+    pub fn parse(s: impl AsRef<str>) -> Result<Self, ParseError> {
+        let fishes = parse_timers(s.as_ref())?.into_iter().map(Fish);
+        Ok(Self(fishes.collect()))
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn step(&mut self) {
+        let new_fish = self
+            .0
+            .iter_mut()
+            .filter_map(Fish::step)
+            .collect::<Vec<_>>();
+        self.0.extend(new_fish);
+    }
+}
+
+/// A single line of comma-separated timers, each between 0 and 8
+fn parse_timers(s: &str) -> Result<Vec<u8>, ParseError> {
+    let line = parse::single_line(s, "a list of timers")?;
+    parse::list(line, ",", |timer| {
+        match timer.parse("a timer from 0 to 8")? {
+            t @ 0..=8 => Ok(t),
+            _ => Err(timer.error("a timer from 0 to 8")),
+        }
+    })
+}
+
+/// How many fish there are after `days` days, simulating each one
+pub fn simulate(mut fishlist: FishList, days: usize) -> usize {
+    for _ in 0..days {
+        fishlist.step();
+    }
+
+    fishlist.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::input_const;
+
+    use super::*;
+
+    fn calculate(s: impl AsRef<str>, days: usize) -> usize {
+        simulate(FishList::parse(s).unwrap(), days)
+    }
+
+    const GIVEN_INPUT: &str = "3,4,3,1,2";
+
+    #[test]
+    fn parse_real_input() {
+        let fishlist = FishList::parse(input_const!(2021, 6)).unwrap();
+        assert_eq!(fishlist.0[0], Fish(1));
+    }
+
+    #[test]
+    fn parse_given_input() {
+        let fishlist = FishList::parse(GIVEN_INPUT).unwrap();
+        assert_eq!(
+            fishlist.0,
+            vec![Fish(3), Fish(4), Fish(3), Fish(1), Fish(2),]
+        );
+    }
+
+    #[test]
+    fn given_input() {
+        assert_eq!(calculate(GIVEN_INPUT, 80), 5934);
+
+        let fishlist = Day6::parse(GIVEN_INPUT).unwrap();
+        assert_eq!(Day6::part1(&fishlist), 5934);
+        assert_eq!(Day6::part2(&fishlist), BigUint::from(26984457539u64));
+    }
+
+    #[test]
+    fn rejects_bad_timers() {
+        let err = FishList::parse("3,4, 9,1").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 6));
+        assert!(FishList::parse("3,-1").is_err());
+        assert!(FishList::parse("\n").is_err());
+        assert!(FishList::parse("3,4\n1").is_err());
+        assert!(FishList::parse("3,4\n").is_ok());
+    }
+
+    proptest! {
+        #[test]
+        fn naive_and_bucketed_agree(
+            timers in proptest::collection::vec(0..=8u8, 1..20),
+            days in 0..64usize,
+        ) {
+            let naive = simulate(FishList(timers.iter().copied().map(Fish).collect()), days);
+            let bucketed = fast::simulate(fast::FishList::from_timers(timers), days);
+            prop_assert_eq!(BigUint::from(naive), bucketed);
+        }
+    }
+}
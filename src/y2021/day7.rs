@@ -0,0 +1,107 @@
+use std::ops::RangeInclusive;
+
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Crabs;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Crabs, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Crabs) -> i64 {
+        min_fuel(input, false)
+    }
+
+    fn part2(input: &Crabs) -> i64 {
+        min_fuel(input, true)
+    }
+}
+
+/// The horizontal position of each crab
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crabs(Vec<i64>);
+
+impl Crabs {
+    fn get_range(&self) -> RangeInclusive<i64> {
+        let min = *self.0.iter().min().unwrap();
+        let max = *self.0.iter().max().unwrap();
+        min..=max
+    }
+
+    fn total_distance(&self, x: i64, q2: bool) -> i64 {
+        self.0
+            .iter()
+            .map(|s| (s - x).abs())
+            .map(distance_fn(q2))
+            .sum()
+    }
+}
+
+fn distance_fn(q2: bool) -> fn(i64) -> i64 {
+    if q2 {
+        triangle
+    } else {
+        id
+    }
+}
+
+fn triangle(x: i64) -> i64 {
+    x * (x + 1) / 2
+}
+
+fn id(x: i64) -> i64 {
+    x
+}
+
+/// The least fuel needed to line every crab up. Each step costs 1 fuel, or with `q2` one more
+/// than the step before.
+pub fn min_fuel(crabs: &Crabs, q2: bool) -> i64 {
+    crabs
+        .get_range()
+        .map(|x| crabs.total_distance(x, q2))
+        .min()
+        .unwrap()
+}
+
+pub fn parse_input(s: impl AsRef<str>) -> Result<Crabs, ParseError> {
+    let line = parse::single_line(s.as_ref(), "a list of positions")?;
+    Ok(Crabs(parse::numbers(line, ",", "a position")?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calculate(s: impl AsRef<str>, q2: bool) -> i64 {
+        min_fuel(&parse_input(s).unwrap(), q2)
+    }
+
+    const GIVEN_INPUT: &str = "16,1,2,0,4,2,7,1,2,14";
+
+    #[test]
+    fn parses_input() {
+        assert_eq!(
+            parse_input(GIVEN_INPUT),
+            Ok(Crabs(vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14]))
+        )
+    }
+
+    #[test]
+    fn rejects_bad_positions() {
+        let err = parse_input("16,1,,0").unwrap_err();
+        assert_eq!((err.column, err.found.as_str()), (6, "nothing"));
+        assert!(parse_input("").is_err());
+    }
+
+    #[test]
+    fn given_example() {
+        assert_eq!(calculate(GIVEN_INPUT, false), 37);
+        assert_eq!(calculate(GIVEN_INPUT, true), 168);
+    }
+}
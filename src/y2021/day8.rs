@@ -1,67 +1,107 @@
 use std::collections::HashMap;
 
-use crate::input_const;
+use crate::parse::{self, ParseError, Span};
+use crate::solution::Solution;
 
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<Entry>;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<Entry>, ParseError> {
+        parse_entries(input)
+    }
+
+    fn part1(input: &Vec<Entry>) -> usize {
+        count_unique_outputs(input)
+    }
+
+    fn part2(input: &Vec<Entry>) -> u64 {
+        sum_outputs(input)
+    }
+}
+
+/// One line of the notes: the ten unique signal patterns, and the four digits of the output
 #[derive(Debug, Clone)]
-struct Entry {
+pub struct Entry {
     uniques: Vec<String>,
     output: Vec<String>,
 }
 
 impl Entry {
-    fn from_str(s: impl AsRef<str>) -> Self {
-        let mut parts = s.as_ref().split("|").map(str::trim);
-        let uniques = parts.next().unwrap();
-        let output = parts.next().unwrap();
-
-        let uniques: Vec<String> = uniques.split(" ").map(ToOwned::to_owned).collect();
-        let output: Vec<String> = output.split(" ").map(ToOwned::to_owned).collect();
-        Self { uniques, output }
+    fn from_str(s: Span) -> Result<Self, ParseError> {
+        let (uniques, output) = parse::pair(s, "|", "a \"|\" between the patterns and the output")?;
+
+        let uniques = parse_patterns(uniques, 10)?;
+        let output = parse_patterns(output, 4)?;
+        Ok(Self { uniques, output })
+    }
+
+    pub fn uniques(&self) -> &[String] {
+        &self.uniques
+    }
+
+    pub fn output(&self) -> &[String] {
+        &self.output
     }
 
-    fn get_output(&self) -> u32 {
+    /// The four digit number the output shows, once the wires have been worked out
+    pub fn get_output(&self) -> u32 {
         let mapping = Mapping::from_uniques(self.uniques.clone());
         let mut output = 0;
         output += mapping.apply_to_digit(self.output[0].clone()) as u32 * 1000;
         output += mapping.apply_to_digit(self.output[1].clone()) as u32 * 100;
         output += mapping.apply_to_digit(self.output[2].clone()) as u32 * 10;
-        output += mapping.apply_to_digit(self.output[3].clone()) as u32 * 1;
+        output += mapping.apply_to_digit(self.output[3].clone()) as u32;
         output
     }
 }
 
-fn is_unique_len(s: impl AsRef<str>) -> bool {
-    match s.as_ref().len() {
-        2 | 3 | 4 | 7 => true,
-        _ => false,
+/// One entry per line
+pub fn parse_entries(s: &str) -> Result<Vec<Entry>, ParseError> {
+    Span::lines(s).map(Entry::from_str).collect()
+}
+
+/// Exactly `count` patterns, each made of distinct segments `a` to `g`
+fn parse_patterns(s: Span, count: usize) -> Result<Vec<String>, ParseError> {
+    let expected = format!("{} patterns", count);
+    let mut patterns = vec![];
+
+    for pattern in s.split_whitespace() {
+        if patterns.len() == count {
+            return Err(pattern.error(expected));
+        }
+        for (i, (c, segment)) in pattern.chars().enumerate() {
+            if !('a'..='g').contains(&segment) || pattern.as_str()[..i].contains(segment) {
+                return Err(c.error("a distinct segment from a to g"));
+            }
+        }
+        patterns.push(pattern.as_str().to_owned());
     }
+
+    if patterns.len() < count {
+        return Err(s.end_error(expected));
+    }
+    Ok(patterns)
 }
 
-pub fn solution1() -> usize {
-    calculate1(input_const!("8"))
+fn is_unique_len(s: impl AsRef<str>) -> bool {
+    matches!(s.as_ref().len(), 2 | 3 | 4 | 7)
 }
 
-fn calculate1(s: impl AsRef<str>) -> usize {
-    let lines = s.as_ref().lines();
-    lines
-        .flat_map(|line| Entry::from_str(line).output)
+/// How many output digits are a 1, 4, 7 or 8, which can be told apart by length alone
+pub fn count_unique_outputs(entries: &[Entry]) -> usize {
+    entries
+        .iter()
+        .flat_map(|entry| &entry.output)
         .filter(|s| is_unique_len(s))
         .count()
 }
 
-pub fn solution2() -> u64 {
-    calculate2(input_const!("8"))
-}
-
-fn calculate2(s: impl AsRef<str>) -> u64 {
-    s.as_ref()
-        .lines()
-        .map(|s| {
-            let entry = Entry::from_str(s);
-            entry.get_output()
-        })
-        .sum::<u32>()
-        .into()
+pub fn sum_outputs(entries: &[Entry]) -> u64 {
+    entries.iter().map(Entry::get_output).sum::<u32>().into()
 }
 
 const CHARS: [char; 7] = ['a', 'b', 'c', 'd', 'e', 'f', 'g'];
@@ -75,14 +115,16 @@ const CHARS: [char; 7] = ['a', 'b', 'c', 'd', 'e', 'f', 'g'];
 // f: 9
 // g: 7
 
+/// Which wire drives which segment of a scrambled display
 #[derive(Debug)]
-struct Mapping {
+pub struct Mapping {
     // inner.insert('a', 'b')  means that the letter at real position 'a' is 'b'
     inner: HashMap<char, char>,
 }
 
 impl Mapping {
-    fn from_uniques(uniques: impl IntoIterator<Item = String>) -> Self {
+    /// Work out the wiring from the ten unique patterns, which must be a valid set of digits
+    pub fn from_uniques(uniques: impl IntoIterator<Item = String>) -> Self {
         let uniques: Vec<String> = uniques.into_iter().collect();
         let mut inner = HashMap::with_capacity(7);
 
@@ -99,8 +141,7 @@ impl Mapping {
 
         let a = CHARS
             .iter()
-            .filter(|c| !one.contains(&c.to_string()) && seven.contains(&c.to_string()))
-            .next()
+            .find(|c| !one.contains(&c.to_string()) && seven.contains(&c.to_string()))
             .unwrap();
 
         inner.insert('a', *a);
@@ -176,7 +217,8 @@ impl Mapping {
         Mapping { inner }
     }
 
-    fn apply_to_digit(&self, digit: String) -> u8 {
+    /// The digit a pattern shows, using this wiring
+    pub fn apply_to_digit(&self, digit: String) -> u8 {
         let mapped = digit
             .chars()
             .map(|c| self.inner.iter().find(|(_, v)| **v == c).unwrap());
@@ -197,7 +239,7 @@ impl Mapping {
             "acf" => 7,
             "abcdefg" => 8,
             "abcdfg" => 9,
-            s => unreachable!(s),
+            s => unreachable!("{}", s),
         }
     }
 }
@@ -212,6 +254,14 @@ fn is_superset(bigger: &str, smaller: &str) -> bool {
 mod tests {
     use super::*;
 
+    fn calculate1(s: impl AsRef<str>) -> usize {
+        count_unique_outputs(&parse_entries(s.as_ref()).unwrap())
+    }
+
+    fn calculate2(s: impl AsRef<str>) -> u64 {
+        sum_outputs(&parse_entries(s.as_ref()).unwrap())
+    }
+
     const GIVEN_INPUT: &str = r#"be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
 edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
 fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
@@ -230,9 +280,11 @@ gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
 
     #[test]
     fn correctly_creates_mapping() {
-        let entry = Entry::from_str(
+        let entry = Day8::parse(
             "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf",
-        );
+        )
+        .unwrap()
+        .remove(0);
         let mapping = Mapping::from_uniques(entry.uniques);
         assert_eq!(
             mapping.inner,
@@ -254,4 +306,20 @@ gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
             "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
         assert_eq!(calculate2(input), 5353);
     }
+
+    #[test]
+    fn rejects_malformed_entries() {
+        let err = Day8::parse("ab cd | ef").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (7, "10 patterns"));
+
+        let line = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab";
+        let err = Day8::parse(line).unwrap_err();
+        assert_eq!(err.found, "end of line");
+
+        let err = Day8::parse(&format!("{} | cdfeb fcxdb cdfeb cdbaf", line)).unwrap_err();
+        assert_eq!(err.column, line.len() + 12);
+
+        let err = Day8::parse(&format!("{} | cdfeb fcadb cdfeb cdbaf ab", line)).unwrap_err();
+        assert_eq!(err.expected, "4 patterns");
+    }
 }
@@ -0,0 +1,141 @@
+use std::collections::HashSet;
+
+use crate::grid::Grid;
+use crate::parse::ParseError;
+use crate::solution::Solution;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Grid<u8>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        parse_heights(input)
+    }
+
+    fn part1(input: &Grid<u8>) -> u64 {
+        risk_levels_for_low_points(input)
+    }
+
+    fn part2(input: &Grid<u8>) -> u64 {
+        largest_basins_product(input)
+    }
+}
+
+/// A heightmap of digits from 0 to 9
+pub fn parse_heights(s: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse_digits(s, "height", 0..=9)
+}
+
+/// Whether every neighbour of `(x, y)` is higher
+pub fn is_low_point(grid: &Grid<u8>, x: usize, y: usize) -> bool {
+    let i = grid[(x, y)];
+    grid.neighbors4(x, y).all(|n| grid[n] > i)
+}
+
+pub fn low_points(grid: &Grid<u8>) -> impl Iterator<Item = (usize, usize)> + '_ {
+    grid.positions().filter(|(x, y)| is_low_point(grid, *x, *y))
+}
+
+pub fn risk_levels_for_low_points(grid: &Grid<u8>) -> u64 {
+    low_points(grid).map(|(x, y)| grid[(x, y)] as u64 + 1).sum()
+}
+
+/// The positions that flow down to each low point. Heights of 9 aren't in any basin.
+pub fn basins(grid: &Grid<u8>) -> Vec<HashSet<(usize, usize)>> {
+    let mut results = vec![];
+    for (x, y) in low_points(grid) {
+        let mut basin = HashSet::from_iter([(x, y)]);
+        while expand_basin(grid, &mut basin) {}
+
+        results.push(basin)
+    }
+
+    results
+}
+
+fn expand_basin(grid: &Grid<u8>, basin: &mut HashSet<(usize, usize)>) -> bool {
+    let possibles: HashSet<(usize, usize)> = basin
+        .iter()
+        .flat_map(|(x, y)| grid.neighbors4(*x, *y))
+        .filter(|&(x, y)| grid[(x, y)] != 9)
+        .collect();
+    let len = basin.len();
+    basin.extend(possibles);
+    basin.len() != len
+}
+
+/// The sizes of the three largest basins, multiplied together
+pub fn largest_basins_product(grid: &Grid<u8>) -> u64 {
+    let mut basins: Vec<_> = basins(grid)
+        .into_iter()
+        .map(|basin| basin.len() as u64)
+        .collect();
+    basins.sort();
+    basins.iter().rev().take(3).product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calculate2(s: impl AsRef<str>) -> u64 {
+        largest_basins_product(&parse_heights(s.as_ref()).unwrap())
+    }
+
+    const GIVEN_INPUT: &str = r#"2199943210
+3987894921
+9856789892
+8767896789
+9899965678"#;
+
+    #[test]
+    fn parse_grid() {
+        let grid = parse_heights(GIVEN_INPUT).unwrap();
+        assert_eq!(grid.height(), 5);
+        assert_eq!(grid.width(), 10);
+        assert_eq!(grid[(0, 0)], 2);
+        assert_eq!(grid[(1, 1)], 9);
+        assert_eq!(grid[(9, 4)], 8);
+    }
+
+    #[test]
+    fn get_neighbors() {
+        let grid = parse_heights(GIVEN_INPUT).unwrap();
+        let neighbors = |x, y| grid.neighbors4(x, y).map(|n| grid[n]).collect::<Vec<_>>();
+        assert_eq!(neighbors(0, 0), vec![1, 3]);
+        assert_eq!(neighbors(1, 1), vec![3, 8, 1, 8]);
+    }
+
+    #[test]
+    fn is_low_point() {
+        let grid = parse_heights(GIVEN_INPUT).unwrap();
+        assert!(!super::is_low_point(&grid, 0, 0));
+        assert!(!super::is_low_point(&grid, 1, 1));
+        assert!(super::is_low_point(&grid, 1, 0));
+        assert!(super::is_low_point(&grid, 9, 0));
+        assert!(super::is_low_point(&grid, 2, 2));
+        assert!(super::is_low_point(&grid, 6, 4));
+    }
+
+    #[test]
+    fn given_input() {
+        let grid = parse_heights(GIVEN_INPUT).unwrap();
+        assert_eq!(risk_levels_for_low_points(&grid), 15);
+
+        assert_eq!(calculate2(GIVEN_INPUT), 1134)
+    }
+
+    #[test]
+    fn rejects_bad_grids() {
+        let err = parse_heights("219\n3a8").err().unwrap();
+        assert_eq!((err.line, err.column), (2, 2));
+
+        let err = parse_heights("219\n39").err().unwrap();
+        assert_eq!((err.line, err.expected.as_str()), (2, "a row of 3 heights"));
+
+        assert!(parse_heights("\n").is_err());
+    }
+}